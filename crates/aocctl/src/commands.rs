@@ -1,15 +1,24 @@
 //! Implementations for individual `aocctl` subcommands.
 
+pub mod answers;
+pub mod auth;
+pub mod history;
 pub mod init;
 pub mod input;
+pub mod leaderboard;
 pub mod new;
 pub mod open;
 pub mod run;
 pub mod solve;
+pub mod splits;
 pub mod status;
 pub mod submit;
+pub mod sync;
 
-// Re-export the run mode enum so the CLI can refer to it as
-// `commands::RunMode`.
+// Re-export the argument and option types the CLI refers to as
+// `commands::*`.
+pub use answers::Days;
+pub use auth::Browser;
 pub use new::TemplateKind;
 pub use run::RunMode;
+pub use submit::SubmitOptions;
@@ -0,0 +1,108 @@
+//! Implementation of the `answers` subcommands (import accepted answers).
+
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crate::prelude::*;
+use crate::utils::ledger::{Attempt, Ledger};
+use crate::utils::response::AocError;
+use crate::utils::verdict::SubmissionVerdict;
+use crate::utils::{calendar, extract, hashes, http};
+
+/// Which days of a year a command should cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Days {
+    /// Every day of the event.
+    #[default]
+    All,
+    /// A single day.
+    One(u8),
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(Self::All);
+        }
+        s.parse()
+            .map(Self::One)
+            .map_err(|_| format!("expected a day number or `all`, got `{s}`"))
+    }
+}
+
+/// Fetch the puzzle pages for `days` of `year` and record every answer the
+/// site shows as accepted in the answer ledger.
+///
+/// Days that have not unlocked are skipped. Answers already in the ledger
+/// are left alone, so running this again only adds what is new. With answer
+/// hashing enabled, every accepted answer is also hashed, including ones the
+/// ledger already knew.
+pub fn pull_answers(year: u32, days: Days) -> Result<()> {
+    let days: Vec<u8> = match days {
+        Days::All => (1..=calendar::days_in_event(year)).collect(),
+        Days::One(day) => {
+            calendar::validate_day(year, day)?;
+            vec![day]
+        }
+    };
+
+    let mut ledger = Ledger::load(year)?;
+    let now = SystemTime::now();
+    let mut imported = 0;
+
+    println!("Pulling accepted answers for {year}...");
+
+    for day in days {
+        if !calendar::is_unlocked(year, day, now) {
+            println!("Day {day:>2}: locked");
+            continue;
+        }
+
+        let page = match http::fetch(&http::Resource::Page { year, day }) {
+            Err(e) if e.downcast_ref::<AocError>() == Some(&AocError::NotUnlocked) => {
+                println!("Day {day:>2}: not available");
+                continue;
+            }
+            page => page?,
+        };
+
+        let answers = extract::extract_accepted_answers(&page);
+        if answers.is_empty() {
+            println!("Day {day:>2}: not solved yet");
+            continue;
+        }
+
+        let mut notes = Vec::new();
+        for (part, answer) in (1..).zip(answers) {
+            hashes::record_accepted(year, day, part, &answer)?;
+
+            match ledger.accepted(day, part) {
+                Some(known) if known == answer => notes.push(format!("part {part} known")),
+                known => {
+                    if let Some(known) = known {
+                        eprintln!(
+                            "warning: day {day} part {part} was recorded as {known}, the site \
+                             shows {answer}"
+                        );
+                    }
+                    notes.push(format!("part {part} = {answer}"));
+                    ledger.record(Attempt {
+                        time: None,
+                        verdict: Some(SubmissionVerdict::Correct),
+                        ..Attempt::now(day, part, &answer)
+                    })?;
+                    imported += 1;
+                }
+            }
+        }
+        println!("Day {day:>2}: {}", notes.join(", "));
+    }
+
+    println!(
+        "Imported {imported} accepted answers into {}",
+        ledger.path().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,213 @@
+//! Implementation of the `auth` subcommands (session token lifecycle).
+
+use std::time::{Duration, SystemTime};
+
+use crate::prelude::*;
+use crate::utils::http::{self, SessionSource};
+use crate::utils::profile::Profile;
+use crate::utils::response::Expect;
+use crate::utils::{cookies, extract};
+
+/// How long an AoC session cookie usually stays valid after logging in.
+const TYPICAL_LIFETIME: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Start warning once this little of the typical lifetime is left.
+const EXPIRY_WARNING: Duration = Duration::from_secs(5 * 24 * 60 * 60);
+
+/// Store a session token in the project `.session` file, or `.session.{name}`
+/// for a named profile.
+///
+/// If `token` is `None`, it is read from standard input. The file is created
+/// with `0600` permissions on Unix, and its modification time doubles as the
+/// date the token was stored.
+pub fn set_token(token: Option<String>) -> Result<()> {
+    let token = match token {
+        Some(t) => t,
+        None => {
+            println!("Paste your AoC session cookie, then press Enter:");
+            let mut buf = String::new();
+            io::stdin().read_line(&mut buf)?;
+            buf
+        }
+    };
+
+    let path = store_token(&token)?;
+    println!("✓ Stored session token in {}", path.display());
+
+    if let Ok(session) = http::find_session()
+        && session.source != SessionSource::File(path.clone())
+    {
+        eprintln!(
+            "warning: the token from {} takes precedence over {}",
+            session.source,
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Write `token` to the active profile's session file that
+/// [`http::read_session`] reads, readable only by the current user. Returns
+/// the file path.
+pub fn store_token(token: &str) -> Result<PathBuf> {
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(anyhow!("Refusing to store an empty session token"));
+    }
+    if !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        eprintln!("warning: AoC session tokens are usually hexadecimal; storing anyway");
+    }
+
+    let path = Profile::active().session_file();
+    write_private(&path, &format!("{token}\n"))?;
+    Ok(path)
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &str) -> Result<()> {
+    use std::io::Write as _;
+    use std::os::unix::fs::{OpenOptionsExt as _, PermissionsExt as _};
+
+    let mut f = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // `mode` only applies to newly created files; tighten existing ones too.
+    f.set_permissions(fs::Permissions::from_mode(0o600))?;
+    f.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Browsers `auth import` can read the session cookie from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum Browser {
+    /// Firefox, from the default profile in `~/.mozilla/firefox`.
+    Firefox,
+    /// Chromium or Google Chrome, from the `Default` profile in `~/.config`.
+    Chromium,
+}
+
+/// Read the `session` cookie from a local browser profile and store it like
+/// [`set_token`] does.
+///
+/// `cookies` points at a specific cookie database instead of the one found
+/// in the browser's default profile.
+pub fn import_token(browser: Browser, cookies: Option<PathBuf>) -> Result<()> {
+    let db = match cookies {
+        Some(path) => path,
+        None => {
+            let home = std::env::var_os("HOME")
+                .map(PathBuf::from)
+                .ok_or_else(|| anyhow!("HOME is not set; pass --cookies <path>"))?;
+            match browser {
+                Browser::Firefox => cookies::firefox_profile(&home)?.join("cookies.sqlite"),
+                Browser::Chromium => cookies::chromium_cookie_db(&home)?,
+            }
+        }
+    };
+
+    println!("Reading cookies from {}...", db.display());
+    let token = match browser {
+        Browser::Firefox => cookies::read_firefox_session(&db)?,
+        Browser::Chromium => cookies::read_chromium_session(&db)?,
+    }
+    .ok_or_else(|| {
+        anyhow!(
+            "No adventofcode.com session cookie in {}; log in with that browser first",
+            db.display()
+        )
+    })?;
+
+    let path = store_token(&token)?;
+    println!(
+        "✓ Imported session token {} into {}",
+        redact(&token),
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Print where the active token comes from, with the token redacted.
+pub fn show_token() -> Result<()> {
+    let session = http::find_session()?;
+
+    println!("Source: {}", session.source);
+    println!("Token:  {}", redact(&session.token));
+
+    if let Some(age) = stored_age(&session.source) {
+        println!("Stored: {} days ago", age.as_secs() / 86_400);
+        warn_if_expiring(age);
+    }
+
+    Ok(())
+}
+
+/// Confirm the token works by fetching an authenticated page.
+pub fn check_token() -> Result<()> {
+    let session = http::find_session()?;
+    println!("Checking token from {}...", session.source);
+
+    let body = http::get("/", Expect::Html)?;
+    let user = extract::extract_user_name(&body).ok_or_else(|| {
+        anyhow!(
+            "The session token from {} was not accepted; log in again and run `aocctl auth \
+             set`",
+            session.source
+        )
+    })?;
+
+    println!("✓ Logged in as {user}");
+
+    if let Some(age) = stored_age(&session.source) {
+        warn_if_expiring(age);
+    }
+
+    Ok(())
+}
+
+/// Show the first and last few characters of a token.
+fn redact(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{head}…{tail} ({} chars)", chars.len())
+}
+
+/// Age of a token stored in a file, based on the file's modification time.
+fn stored_age(source: &SessionSource) -> Option<Duration> {
+    let SessionSource::File(path) = source else {
+        return None;
+    };
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    SystemTime::now().duration_since(modified).ok()
+}
+
+fn warn_if_expiring(age: Duration) {
+    let days = |d: Duration| d.as_secs() / 86_400;
+
+    if age >= TYPICAL_LIFETIME {
+        eprintln!(
+            "warning: this token was stored {} days ago and has probably expired",
+            days(age)
+        );
+    } else if TYPICAL_LIFETIME - age <= EXPIRY_WARNING {
+        eprintln!(
+            "warning: this token will probably expire in about {} days",
+            days(TYPICAL_LIFETIME - age).max(1)
+        );
+    }
+}
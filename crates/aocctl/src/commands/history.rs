@@ -0,0 +1,50 @@
+//! Implementation of the `history` subcommand (answer ledger view).
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::prelude::*;
+use crate::utils::calendar;
+use crate::utils::ledger::Ledger;
+
+/// Print every recorded attempt for `year`, optionally only for `day`.
+pub fn show_history(year: u32, day: Option<u8>) -> Result<()> {
+    let ledger = Ledger::load(year)?;
+    let attempts: Vec<_> = ledger.for_day(day).collect();
+
+    if attempts.is_empty() {
+        match day {
+            Some(day) => println!("No answers recorded for {year} day {day}"),
+            None => println!("No answers recorded for {year}"),
+        }
+        return Ok(());
+    }
+
+    let width = attempts
+        .iter()
+        .map(|a| a.answer.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    println!(
+        "{:<23}  {:>3}  {:>4}  {:<width$}  Verdict",
+        "Time", "Day", "Part", "Answer"
+    );
+    for a in attempts {
+        let time = a.time.map_or_else(
+            || "-".to_string(),
+            |secs| calendar::format_utc(UNIX_EPOCH + Duration::from_secs(secs)),
+        );
+        let verdict = match (&a.verdict, a.dry_run) {
+            (_, true) => "dry run".to_string(),
+            (Some(v), false) => v.to_string(),
+            (None, false) => "-".to_string(),
+        };
+        println!(
+            "{time:<23}  {:>3}  {:>4}  {:<width$}  {verdict}",
+            a.day, a.part, a.answer
+        );
+    }
+
+    Ok(())
+}
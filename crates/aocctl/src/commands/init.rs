@@ -1,13 +1,15 @@
 //! Implementation of the `init` subcommand.
 
 use crate::prelude::*;
+use crate::utils::profile::Profile;
 
 /// Initialize a new Advent of Code year crate and related directories.
 ///
 /// This creates:
 ///   * `crates/aoc{year}` with a minimal `Cargo.toml` and `src/lib.rs`,
 ///   * `crates/aoc{year}/src/bin` for day binaries,
-///   * `inputs/{year}` and `answers/{year}` directories.
+///   * `inputs/{year}` and `answers/{year}` directories (scoped to the active
+///     profile).
 pub fn init_year(year: u32) -> Result<()> {
     let pkg = format!("aoc{year}");
     let crate_dir = Path::new("crates").join(&pkg);
@@ -19,8 +21,9 @@ pub fn init_year(year: u32) -> Result<()> {
 
     fs::create_dir_all(crate_dir.join("src/bin"))?;
     fs::create_dir_all(crate_dir.join("src"))?;
-    fs::create_dir_all(Path::new("inputs").join(year.to_string()))?;
-    fs::create_dir_all(Path::new("answers").join(year.to_string()))?;
+    let profile = Profile::active();
+    fs::create_dir_all(profile.inputs_dir(year))?;
+    fs::create_dir_all(profile.answers_dir(year))?;
 
     let cargo_toml = {
         let txt = include_str!("../../templates/cargo.txt");
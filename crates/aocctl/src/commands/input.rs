@@ -1,43 +1,103 @@
 //! Implementation of the `input` subcommand (download puzzle input).
 
+use std::thread;
+use std::time::{Duration, SystemTime};
+
 use crate::prelude::*;
-use crate::utils::http;
+use crate::utils::profile::Profile;
+use crate::utils::response::AocError;
+use crate::utils::{calendar, http, splits};
 
-/// Download the puzzle input for the given year and day and save it under
-/// `inputs/{year}/d{day:02}.txt`.
-pub fn download_input(year: u32, day: u8) -> Result<()> {
-    let session = http::read_session()?;
-    let client = http::http_client()?;
-    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
+/// Pause between attempts while the site still reports the puzzle as locked.
+const UNLOCK_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// How many times to ask again after the unlock instant before giving up.
+const UNLOCK_RETRIES: u32 = 30;
+
+/// Outcome of [`save_input`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Download {
+    /// The input was fetched and written to this path.
+    Saved(PathBuf),
+    /// The input file already existed and was left alone.
+    Exists(PathBuf),
+}
+
+/// Where the input for `year`/`day` is stored for the active profile.
+pub fn input_path(year: u32, day: u8) -> PathBuf {
+    Profile::active()
+        .inputs_dir(year)
+        .join(format!("d{day:02}.txt"))
+}
 
-    println!("Downloading input for {year} day {day}...");
+/// Save the example blocks in `blocks` next to the input for `year`/`day`
+/// as `dNN.example{n}.txt`, skipping any that are already saved.
+///
+/// Numbering continues after the highest existing example file. Returns the
+/// paths that were written.
+pub fn save_examples(year: u32, day: u8, blocks: &[String]) -> Result<Vec<PathBuf>> {
+    let dir = Profile::active().inputs_dir(year);
+    let prefix = format!("d{day:02}.example");
 
-    let resp = client
-        .get(&url)
-        .header(reqwest::header::COOKIE, format!("session={session}"))
-        .send()?;
+    let mut next = 1;
+    let mut saved = Vec::new();
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for path in entries.filter_map(|e| e.ok().map(|e| e.path())) {
+            let n = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(&prefix)?.strip_suffix(".txt"))
+                .and_then(|n| n.parse::<u32>().ok());
+            if let Some(n) = n {
+                next = next.max(n + 1);
+                saved.push(fs::read_to_string(&path)?);
+            }
+        }
+    }
 
-    if !resp.status().is_success() {
-        return Err(anyhow!(
-            "HTTP {} while downloading input",
-            resp.status().as_u16()
-        ));
+    let mut written = Vec::new();
+    for block in blocks {
+        if saved.contains(block) {
+            continue;
+        }
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!("{prefix}{next}.txt"));
+        fs::write(&path, block)?;
+        saved.push(block.clone());
+        written.push(path);
+        next += 1;
     }
 
-    let body = resp.text()?;
-    let trimmed = body.trim_end_matches('\n');
+    Ok(written)
+}
 
-    let dir = Path::new("inputs").join(year.to_string());
-    fs::create_dir_all(&dir)?;
+/// Download the puzzle input for the given year and day and save it under
+/// `inputs/{year}/d{day:02}.txt`, or `inputs/{profile}/{year}` for a named
+/// profile, then print a short preview.
+///
+/// See [`save_input`] for how `force` and `wait` behave.
+pub fn download_input(year: u32, day: u8, force: bool, wait: bool) -> Result<()> {
+    if force || !input_path(year, day).exists() {
+        println!("Downloading input for {year} day {day}...");
+    }
 
-    let path = dir.join(format!("d{day:02}.txt"));
-    fs::write(&path, trimmed)?;
+    let path = match save_input(year, day, force, wait)? {
+        Download::Saved(path) => path,
+        Download::Exists(path) => {
+            println!(
+                "Input already exists at {}; pass --force to download it again",
+                path.display()
+            );
+            return Ok(());
+        }
+    };
 
-    let line_count = trimmed.lines().count();
+    let text = fs::read_to_string(&path)?;
+    let line_count = text.lines().count();
     println!("✓ Saved to {}", path.display());
     println!("  Lines: {line_count}");
 
-    for (i, line) in trimmed.lines().take(3).enumerate() {
+    for (i, line) in text.lines().take(3).enumerate() {
         println!("  {}: {}", i + 1, line);
     }
     if line_count > 3 {
@@ -46,3 +106,78 @@ pub fn download_input(year: u32, day: u8) -> Result<()> {
 
     Ok(())
 }
+
+/// Fetch the input for `year`/`day` and write it to [`input_path`].
+///
+/// A previously downloaded copy in the cache is reused, so this also works
+/// with `--offline` once the input has been fetched at least once.
+///
+/// Inputs never change, so an existing file is left alone unless `force` is
+/// set, in which case the cached copy is dropped and the input fetched again.
+///
+/// The first download of each day is recorded as a split time.
+///
+/// With `wait`, a countdown is shown until the puzzle unlocks and the
+/// download is retried for a while if the site is still a few seconds behind.
+pub fn save_input(year: u32, day: u8, force: bool, wait: bool) -> Result<Download> {
+    calendar::validate_day(year, day)?;
+
+    let path = input_path(year, day);
+    let resource = http::Resource::Input { year, day };
+
+    if path.exists() && !force {
+        return Ok(Download::Exists(path));
+    }
+    if force {
+        http::invalidate(&resource)?;
+    }
+
+    if wait {
+        wait_for_unlock(year, day)?;
+    }
+
+    let body = if wait { fetch_when_unlocked(&resource)? } else { http::fetch(&resource)? };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, body.trim_end_matches('\n'))?;
+    splits::record_input(year, day)?;
+
+    Ok(Download::Saved(path))
+}
+
+/// Show a countdown until the puzzle for `year`/`day` unlocks.
+fn wait_for_unlock(year: u32, day: u8) -> Result<()> {
+    if calendar::is_unlocked(year, day, SystemTime::now()) {
+        return Ok(());
+    }
+
+    let unlock = calendar::unlock_time(year, day);
+    println!(
+        "{year} day {day} unlocks at {}",
+        calendar::format_utc(unlock)
+    );
+    calendar::wait_until(unlock, &format!("Day {day} unlocks"))
+}
+
+/// Fetch `resource`, asking again while the site reports it as not unlocked.
+fn fetch_when_unlocked(resource: &http::Resource) -> Result<String> {
+    for attempt in 0.. {
+        match http::fetch(resource) {
+            Err(e)
+                if attempt < UNLOCK_RETRIES
+                    && e.downcast_ref::<AocError>() == Some(&AocError::NotUnlocked) =>
+            {
+                eprintln!(
+                    "Not available yet; retrying in {}s",
+                    UNLOCK_RETRY_DELAY.as_secs()
+                );
+                thread::sleep(UNLOCK_RETRY_DELAY);
+            }
+            result => return result,
+        }
+    }
+
+    unreachable!("the retry loop always returns")
+}
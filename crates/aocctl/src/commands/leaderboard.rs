@@ -0,0 +1,279 @@
+//! Implementation of the `leaderboard` subcommand (private leaderboards).
+
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+
+use crate::prelude::*;
+use crate::utils::config::Config;
+use crate::utils::leaderboard::{DayStats, Leaderboard, Member, MemberStats, StarEvent};
+use crate::utils::response::AocError;
+use crate::utils::{calendar, detect, http};
+
+/// Body POSTed to the webhook for every poll that found new stars.
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    leaderboard: u64,
+    year:        u32,
+    events:      Vec<WebhookEvent<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct WebhookEvent<'a> {
+    #[serde(flatten)]
+    star:    &'a StarEvent,
+    /// The line printed for this event.
+    message: String,
+}
+
+/// Load private leaderboard `id`, from `file` if given or else from the site.
+///
+/// The year of a downloaded leaderboard is `year` or the detected one; a file
+/// carries its own.
+pub fn load(year: Option<u32>, id: u64, file: Option<&Path>) -> Result<Leaderboard> {
+    match file {
+        Some(path) => Leaderboard::load_file(path),
+        None => Leaderboard::fetch(detect::resolve_year(year)?, id),
+    }
+}
+
+/// Print private leaderboard `id` as a table.
+///
+/// Each member gets a rank, local score, star count and one marker per day:
+/// `*` for both parts, `+` for part one only and `.` for neither.
+pub fn show_leaderboard(year: Option<u32>, id: u64, file: Option<&Path>) -> Result<()> {
+    let board = load(year, id, file)?;
+    let year = board.year()?;
+    let days = calendar::days_in_event(year);
+
+    println!(
+        "Private leaderboard {id} for {year} ({} members)",
+        board.members.len()
+    );
+    println!();
+
+    let tens: String = (1..=days)
+        .map(|d| if d >= 10 { char::from(b'0' + d / 10) } else { ' ' })
+        .collect();
+    let units: String = (1..=days).map(|d| char::from(b'0' + d % 10)).collect();
+    println!("{:>4} {:>5} {:>5}  {tens}", "", "", "");
+    println!("{:>4} {:>5} {:>5}  {units}  Name", "Rank", "Score", "Stars");
+
+    for (rank, member) in board.ranked().into_iter().enumerate() {
+        println!(
+            "{:>4} {:>5} {:>5}  {}  {}",
+            format!("{})", rank + 1),
+            member.local_score,
+            member.stars,
+            markers(member, days),
+            member.display_name()
+        );
+    }
+    println!("(* both parts, + part one, . neither)");
+
+    Ok(())
+}
+
+/// Print statistics for leaderboard `id`: recomputed scores with the rank
+/// after each day, and every member's time from unlock to each star.
+///
+/// Stars on the `excluded` days do not count towards the recomputed score.
+/// With `csv` one row per member and day is printed instead, with times in
+/// seconds.
+pub fn show_stats(
+    year: Option<u32>,
+    id: u64,
+    file: Option<&Path>,
+    excluded: &[u8],
+    csv: bool,
+) -> Result<()> {
+    let board = load(year, id, file)?;
+    let stats = board.stats(excluded)?;
+    if csv {
+        print_csv(&stats);
+        return Ok(());
+    }
+
+    let year = board.year()?;
+    let width = stats
+        .iter()
+        .map(|s| s.member.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len());
+
+    print!("Scores for leaderboard {id} ({year})");
+    if !excluded.is_empty() {
+        let days: Vec<String> = excluded.iter().map(u8::to_string).collect();
+        let plural = if days.len() == 1 { "" } else { "s" };
+        print!(", ignoring day{plural} {}", days.join(", "));
+    }
+    println!(":");
+    println!(
+        "{:>4} {:>5} {:>5}  {:<width$}  Rank after each day",
+        "Rank", "Score", "Site", "Name"
+    );
+    for (rank, s) in stats.iter().enumerate() {
+        let ranks: Vec<String> = s.days.iter().map(|d| d.rank.to_string()).collect();
+        println!(
+            "{:>4} {:>5} {:>5}  {:<width$}  {}",
+            format!("{})", rank + 1),
+            s.score,
+            s.member.local_score,
+            s.member.display_name(),
+            ranks.join(" ")
+        );
+    }
+
+    println!();
+    println!("Time from unlock to each star:");
+    println!(
+        "{:<width$}  {:>3}  {:>12}  {:>12}  {:>12}",
+        "Name", "Day", "Part 1", "Part 2", "Δ"
+    );
+    for s in &stats {
+        for d in s.days.iter().filter(|d| d.part1.is_some()) {
+            println!(
+                "{:<width$}  {:>3}  {:>12}  {:>12}  {:>12}",
+                s.member.display_name(),
+                d.day,
+                duration_cell(d.part1),
+                duration_cell(d.part2),
+                duration_cell(d.delta())
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn duration_cell(d: Option<Duration>) -> String {
+    d.map_or_else(|| "-".to_string(), calendar::format_duration)
+}
+
+fn print_csv(stats: &[MemberStats<'_>]) {
+    let secs = |d: Option<Duration>| d.map(|d| d.as_secs().to_string()).unwrap_or_default();
+
+    println!("member_id,member,day,part1_secs,part2_secs,delta_secs,points,score,rank");
+    for s in stats {
+        for d in &s.days {
+            let DayStats {
+                day,
+                points,
+                score,
+                rank,
+                ..
+            } = *d;
+            println!(
+                "{},{},{day},{},{},{},{points},{score},{rank}",
+                s.member.id,
+                csv_field(&s.member.display_name()),
+                secs(d.part1),
+                secs(d.part2),
+                secs(d.delta())
+            );
+        }
+    }
+}
+
+/// Quote `value` for CSV if it contains a separator or a quote.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn markers(member: &Member, days: u8) -> String {
+    (1..=days)
+        .map(|day| match member.parts_on(day) {
+            0 => '.',
+            1 => '+',
+            _ => '*',
+        })
+        .collect()
+}
+
+/// Poll leaderboard `id` every 15 minutes and print each new star, until
+/// interrupted.
+///
+/// Every poll is compared with the previous snapshot, which is saved under
+/// the state directory so a restarted watcher does not report old stars
+/// again. With `leaderboard_webhook` set in the config, the new stars are
+/// also POSTed there as JSON; the snapshot only moves on once that succeeds,
+/// so undelivered stars are sent again with the next poll.
+///
+/// An error in the first poll, or a rejected session, ends the watch, since
+/// it will not go away by itself. Later failures are reported and retried at
+/// the next interval.
+pub fn watch_leaderboard(year: Option<u32>, id: u64, file: Option<&Path>) -> Result<()> {
+    let webhook = Config::load()?.leaderboard_webhook;
+
+    poll(year, id, file, webhook.as_deref())?;
+    loop {
+        let next = SystemTime::now() + http::LEADERBOARD_MAX_AGE;
+        println!("Next check at {}", calendar::format_utc(next));
+        thread::sleep(http::LEADERBOARD_MAX_AGE);
+
+        if let Err(e) = poll(year, id, file, webhook.as_deref()) {
+            if matches!(
+                e.downcast_ref::<AocError>(),
+                Some(AocError::InvalidSession { .. } | AocError::LoginPage)
+            ) {
+                return Err(e);
+            }
+            eprintln!("warning: {e}");
+        }
+    }
+}
+
+fn poll(year: Option<u32>, id: u64, file: Option<&Path>, webhook: Option<&str>) -> Result<()> {
+    let board = load(year, id, file)?;
+    let year = board.year()?;
+    let path = Leaderboard::snapshot_path(year, id);
+
+    match Leaderboard::load_snapshot(&path)? {
+        None => println!(
+            "Watching leaderboard {id} for {year}: {} members, {} stars so far",
+            board.members.len(),
+            board.members.values().map(|m| m.stars).sum::<u32>()
+        ),
+        Some(previous) => {
+            let events = board.new_stars_since(&previous);
+            if events.is_empty() {
+                println!("No new stars");
+            }
+            for event in &events {
+                println!("{event}");
+            }
+
+            if let Some(url) = webhook
+                && !events.is_empty()
+                && let Err(e) = notify(url, id, year, &events)
+            {
+                // Keep the old snapshot so these stars are sent again.
+                eprintln!("warning: could not notify {url}: {e}; retrying with the next check");
+                return Ok(());
+            }
+        }
+    }
+
+    board.save_snapshot(&path)
+}
+
+fn notify(url: &str, id: u64, year: u32, events: &[StarEvent]) -> Result<()> {
+    let payload = WebhookPayload {
+        leaderboard: id,
+        year,
+        events: events
+            .iter()
+            .map(|star| WebhookEvent {
+                star,
+                message: star.to_string(),
+            })
+            .collect(),
+    };
+    http::post_json(url, &serde_json::to_string(&payload)?)
+}
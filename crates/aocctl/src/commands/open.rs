@@ -3,13 +3,14 @@
 use std::process::{Command, Stdio};
 
 use crate::prelude::*;
+use crate::utils::http;
 
 /// Open the Advent of Code puzzle page in the default browser.
 ///
 /// This tries `xdg-open` (Linux) and `open` (macOS). On failure, it prints
 /// the URL so that the user can open it manually.
 pub fn open_day(year: u32, day: u8) -> Result<()> {
-    let url = format!("https://adventofcode.com/{year}/day/{day}");
+    let url = http::url(&format!("/{year}/day/{day}"))?;
     println!("Opening {url}");
 
     if try_open("xdg-open", &url)? || try_open("open", &url)? {
@@ -3,21 +3,48 @@
 use std::process::{Command, Stdio};
 
 use crate::commands::run::RunMode;
-use crate::commands::submit::submit_answer_ext;
+use crate::commands::submit::{SubmitOptions, submit_answer_ext};
 use crate::prelude::*;
-// use crate::utils::extract::extract_article_text; /* not strictly required here, but *
-// useful later */
 use crate::utils::input::resolve_input_path;
+use crate::utils::ledger::Ledger;
+use crate::utils::verdict::SubmissionVerdict;
+use crate::utils::{extract, hashes, http};
 
 /// Run the day binary, inspect its output, prompt the user which part to
 /// submit and then perform the submission (or a dry run).
 ///
+/// With `auto` there is no prompt: the first part the day page does not show
+/// as solved is submitted. Nothing is built or sent if both are solved.
+///
+/// Returns the verdict for the submitted part, or `None` if nothing was sent.
+///
 /// This expects the day binary to print lines that start with:
 ///   * `Part 1:`
 ///   * `Part 2:`
-pub fn solve_day(year: u32, day: u8, input: &str, mode: RunMode, dry_run: bool) -> Result<()> {
+pub fn solve_day(
+    year: u32,
+    day: u8,
+    input: &str,
+    mode: RunMode,
+    auto: bool,
+    options: SubmitOptions,
+) -> Result<Option<SubmissionVerdict>> {
     println!("Solving {year} day {day}...");
 
+    let next_part = if auto {
+        match next_unsolved_part(year, day)? {
+            Some(part) => Some(part),
+            None => {
+                println!(
+                    "Both parts of {year} day {day} are already solved; nothing to submit."
+                );
+                return Ok(None);
+            }
+        }
+    } else {
+        None
+    };
+
     let pkg = format!("aoc{year}");
     let bin_name = format!("d{day:02}");
     let input_path = resolve_input_path(year, day, input)?;
@@ -88,18 +115,36 @@ pub fn solve_day(year: u32, day: u8, input: &str, mode: RunMode, dry_run: bool)
     println!("  Part 1 = {:?}", part1);
     println!("  Part 2 = {:?}", part2);
 
-    println!("Submit which part? Enter 1, 2, or press Enter to skip:");
+    // Compare against answers the site already accepted, if any are known.
+    let ledger = Ledger::load(year)?;
+    for (part, output) in [(1, &part1), (2, &part2)] {
+        let Some(output) = output else { continue };
+        match hashes::check_accepted(year, &ledger, day, part, output)? {
+            Some(true) => println!("  ✓ Part {part} matches the accepted answer"),
+            Some(false) => println!("  ✗ Part {part} differs from the accepted answer"),
+            None => {}
+        }
+    }
 
-    let mut buf = String::new();
-    io::stdin().read_line(&mut buf)?;
-    let choice = buf.trim();
+    let choice = match next_part {
+        Some(part) => {
+            println!("Part {part} is the first unsolved part.");
+            part.to_string()
+        }
+        None => {
+            println!("Submit which part? Enter 1, 2, or press Enter to skip:");
+            let mut buf = String::new();
+            io::stdin().read_line(&mut buf)?;
+            buf.trim().to_string()
+        }
+    };
 
-    let (part, answer_opt) = match choice {
+    let (part, answer_opt) = match choice.as_str() {
         "1" => (1, part1),
         "2" => (2, part2),
         "" => {
             println!("Skipping submission.");
-            return Ok(());
+            return Ok(None);
         }
         _ => return Err(anyhow!("Invalid selection: {choice}")),
     };
@@ -109,5 +154,20 @@ pub fn solve_day(year: u32, day: u8, input: &str, mode: RunMode, dry_run: bool)
     println!("Preparing to submit part {part}: {answer}");
 
     // Call into the shared submission logic.
-    submit_answer_ext(year, day, part, answer, dry_run)
+    submit_answer_ext(year, day, part, answer, options)
+}
+
+/// The first part of `year`/`day` that is not solved yet, judged by the
+/// accepted answers on the authenticated day page, or `None` if both are.
+///
+/// The page is always downloaded again: a cached copy could miss a part solved
+/// in the browser since, and the wrong level would be submitted.
+fn next_unsolved_part(year: u32, day: u8) -> Result<Option<u8>> {
+    http::ensure_online("pick the next unsolved part for --auto")?;
+
+    let page = http::Resource::Page { year, day };
+    http::invalidate(&page)?;
+    let body = http::fetch(&page)?;
+    let solved = extract::extract_accepted_answers(&body).len();
+    Ok((solved < 2).then_some(solved as u8 + 1))
 }
@@ -0,0 +1,41 @@
+//! Implementation of the `splits` subcommand (personal split times).
+
+use crate::prelude::*;
+use crate::utils::calendar;
+use crate::utils::splits::{DaySplits, Splits};
+
+/// Print a table of split times for `year`, measured from each day's unlock.
+///
+/// `Δ` is the time from part one to part two.
+pub fn show_splits(year: u32) -> Result<()> {
+    let splits = Splits::load(year)?;
+    if splits.days.is_empty() {
+        println!("No split times recorded for {year}");
+        return Ok(());
+    }
+
+    let cell = |d: Option<std::time::Duration>| {
+        d.map_or_else(|| "-".to_string(), calendar::format_duration)
+    };
+
+    println!("Split times for {year}, from unlock (midnight UTC-5):");
+    println!(
+        "{:>3}  {:>12}  {:>12}  {:>12}  {:>12}",
+        "Day", "Input", "Part 1", "Part 2", "Δ"
+    );
+    for (&day, times) in &splits.days {
+        let part1 = DaySplits::elapsed(year, day, times.part1);
+        let part2 = DaySplits::elapsed(year, day, times.part2);
+        let delta = part1.zip(part2).map(|(a, b)| b.saturating_sub(a));
+
+        println!(
+            "{day:>3}  {:>12}  {:>12}  {:>12}  {:>12}",
+            cell(DaySplits::elapsed(year, day, times.input)),
+            cell(part1),
+            cell(part2),
+            cell(delta)
+        );
+    }
+
+    Ok(())
+}
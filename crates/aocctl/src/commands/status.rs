@@ -1,21 +1,73 @@
 //! Implementation of the `status` subcommand.
 
+use serde::Serialize;
+
 use crate::prelude::*;
-use crate::utils::http;
+use crate::utils::{calendar, detect, extract, http};
+
+/// Stars earned for one day, as printed by `status --json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DayStars {
+    pub day:      u8,
+    /// `false` while the puzzle has not been released.
+    pub unlocked: bool,
+    pub stars:    u8,
+}
+
+/// Stars earned for a whole year, as printed by `status --json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct YearStars {
+    pub year:  u32,
+    pub stars: u32,
+    pub days:  Vec<DayStars>,
+}
 
-/// Fetch the puzzle page and count how many stars have been earned for the
-/// given year and day.
+/// Progress on one event, as printed by `status --all-years --json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct YearProgress {
+    pub year:        u32,
+    pub stars:       u32,
+    pub max_stars:   u32,
+    /// Whether `crates/aoc{year}` exists.
+    pub local_crate: bool,
+    /// Days with a `src/bin/dNN.rs` solution.
+    pub local_days:  Vec<u8>,
+    /// Days with stars but no local solution.
+    pub no_solution: Vec<u8>,
+    /// Days with a local solution but no stars.
+    pub no_stars:    Vec<u8>,
+}
+
+/// Show the stars earned for `year`, or only for `day` when given.
 ///
-/// This parses the HTML and counts `class="star"` occurrences, which is simple
-/// but works well enough for this purpose.
-pub fn check_status(year: u32, day: u8) -> Result<()> {
-    let client = http::http_client()?;
-    let url = format!("https://adventofcode.com/{year}/day/{day}");
+/// Both the puzzle page and the calendar are fetched with the session, since
+/// a logged-out visitor never sees any stars. A single day's stars are the
+/// `Your puzzle answer was` paragraphs on its puzzle page; the year view
+/// reads every day from the calendar page. With `json` the result is printed
+/// as JSON for scripts instead.
+pub fn check_status(year: u32, day: Option<u8>, json: bool) -> Result<()> {
+    let Some(day) = day else {
+        return show_year(year, json);
+    };
+    calendar::validate_day(year, day)?;
 
-    println!("Fetching status from {url}");
+    let page = http::Resource::Page { year, day };
+    if !json {
+        println!("Fetching status from {}", http::url(&page.url_path())?);
+    }
 
-    let body = client.get(&url).send()?.text()?;
-    let stars = body.matches("class=\"star\"").count();
+    let body = http::fetch(&page)?;
+    let stars = extract::extract_accepted_answers(&body).len().min(2) as u8;
+
+    if json {
+        let day = DayStars {
+            day,
+            unlocked: true,
+            stars,
+        };
+        println!("{}", serde_json::to_string_pretty(&day)?);
+        return Ok(());
+    }
 
     println!("Stars for {year} day {day}: {stars}");
     match stars {
@@ -26,3 +78,158 @@ pub fn check_status(year: u32, day: u8) -> Result<()> {
 
     Ok(())
 }
+
+/// Read every day's stars for `year` from its calendar page.
+pub fn year_stars(year: u32) -> Result<YearStars> {
+    let body = http::fetch(&http::Resource::Calendar { year })?;
+    let unlocked = extract::extract_calendar_stars(&body);
+
+    let days: Vec<DayStars> = (1..=calendar::days_in_event(year))
+        .map(|day| {
+            let found = unlocked.iter().find(|&&(d, _)| d == day);
+            DayStars {
+                day,
+                unlocked: found.is_some(),
+                stars: found.map_or(0, |&(_, stars)| stars),
+            }
+        })
+        .collect();
+
+    Ok(YearStars {
+        year,
+        stars: days.iter().map(|d| u32::from(d.stars)).sum(),
+        days,
+    })
+}
+
+fn show_year(year: u32, json: bool) -> Result<()> {
+    let stars = year_stars(year)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stars)?);
+        return Ok(());
+    }
+
+    println!(
+        "Stars for {year}: {}/{}",
+        stars.stars,
+        2 * u32::from(calendar::days_in_event(year))
+    );
+    for row in stars.days.chunks(5) {
+        let cells: Vec<String> = row.iter().map(render_day).collect();
+        println!("{}", cells.join("   ").trim_end());
+    }
+    println!("(** both parts, * part one, - unsolved, blank: locked)");
+
+    Ok(())
+}
+
+/// A grid cell: the day number and its stars.
+fn render_day(day: &DayStars) -> String {
+    let stars = match (day.unlocked, day.stars) {
+        (false, _) => "",
+        (true, 0) => "-",
+        (true, 1) => "*",
+        _ => "**",
+    };
+    format!("{:>2} {stars:<2}", day.day)
+}
+
+/// Show the stars of every event next to the local `crates/aocYYYY` crates.
+///
+/// Star totals come from the `/events` page. The calendar of each year that
+/// has stars is then fetched to find starred days without a local
+/// `src/bin/dNN.rs`; local solutions for days without stars are listed too.
+pub fn check_all_years(json: bool) -> Result<()> {
+    let events = http::fetch(&http::Resource::Events)?;
+    let mut totals = extract::extract_event_stars(&events);
+    let local_years = detect::local_years()?;
+    for &year in &local_years {
+        if !totals.iter().any(|&(y, _)| y == year) {
+            totals.push((year, 0));
+        }
+    }
+    totals.sort_unstable_by_key(|&(year, _)| std::cmp::Reverse(year));
+
+    let mut progress = Vec::new();
+    for (year, stars) in totals {
+        let local_days = detect::local_days(year)?;
+        let starred: Vec<u8> = if stars > 0 {
+            year_stars(year)?
+                .days
+                .iter()
+                .filter(|d| d.stars > 0)
+                .map(|d| d.day)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        progress.push(YearProgress {
+            year,
+            stars,
+            max_stars: 2 * u32::from(calendar::days_in_event(year)),
+            local_crate: local_years.contains(&year),
+            no_solution: starred
+                .iter()
+                .copied()
+                .filter(|d| !local_days.contains(d))
+                .collect(),
+            no_stars: local_days
+                .iter()
+                .copied()
+                .filter(|d| !starred.contains(d))
+                .collect(),
+            local_days,
+        });
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&progress)?);
+        return Ok(());
+    }
+
+    println!("{:<4}  {:>5}  Local", "Year", "Stars");
+    for p in &progress {
+        let local = if p.local_crate {
+            let n = p.local_days.len();
+            format!(
+                "crates/aoc{} ({n} day{})",
+                p.year,
+                if n == 1 { "" } else { "s" }
+            )
+        } else {
+            "-".to_string()
+        };
+        println!(
+            "{:<4}  {:>5}  {local}",
+            p.year,
+            format!("{}/{}", p.stars, p.max_stars)
+        );
+    }
+
+    let mismatches = |title: &str, days: fn(&YearProgress) -> &[u8]| {
+        let rows: Vec<String> = progress
+            .iter()
+            .filter(|p| !days(p).is_empty())
+            .map(|p| format!("  {}: {}", p.year, join_days(days(p))))
+            .collect();
+        if !rows.is_empty() {
+            println!();
+            println!("{title}");
+            println!("{}", rows.join("\n"));
+        }
+    };
+    mismatches("Days with stars but no local solution:", |p| &p.no_solution);
+    mismatches("Local solutions without stars:", |p| &p.no_stars);
+
+    Ok(())
+}
+
+fn join_days(days: &[u8]) -> String {
+    let days: Vec<String> = days.iter().map(u8::to_string).collect();
+    format!(
+        "day{} {}",
+        if days.len() == 1 { "" } else { "s" },
+        days.join(", ")
+    )
+}
@@ -1,22 +1,43 @@
 //! Implementation of the `submit` subcommand.
 
-use std::fs::OpenOptions;
+use std::time::{Duration, SystemTime};
 
+use crate::commands::input;
 use crate::prelude::*;
-use crate::utils::{extract, http};
+use crate::utils::ledger::{Attempt, Ledger};
+use crate::utils::validate::{self, AnswerCheck};
+use crate::utils::verdict::SubmissionVerdict;
+use crate::utils::{calendar, extract, hashes, http, splits};
+
+/// How long to wait when the site rate-limits without saying for how long.
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Flags shared by `submit` and `solve` that control how an answer is sent.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubmitOptions {
+    /// Print what would be sent instead of contacting the server.
+    pub dry_run:    bool,
+    /// Send answers the ledger shows to be wrong, and suspicious-looking
+    /// answers without asking.
+    pub force:      bool,
+    /// Wait out a rate limit and resubmit instead of giving up.
+    pub wait:       bool,
+    /// Never prompt, e.g. for `solve --auto`: suspicious answers are refused
+    /// unless `force` is set.
+    pub unattended: bool,
+}
 
 /// Submit an answer for a particular day and part.
 ///
 /// If `answer_arg` is `None`, this function will prompt on standard input.
-/// If `dry_run` is true, this prints what would be sent and returns without
-/// contacting the Advent of Code server.
+/// See [`submit_answer_ext`] for the options.
 pub fn submit_answer(
     year: u32,
     day: u8,
     part: u8,
     answer_arg: Option<String>,
-    dry_run: bool,
-) -> Result<()> {
+    options: SubmitOptions,
+) -> Result<Option<SubmissionVerdict>> {
     if part != 1 && part != 2 {
         return Err(anyhow!("Part must be 1 or 2, got {part}"));
     }
@@ -31,78 +52,193 @@ pub fn submit_answer(
         }
     };
 
-    if answer.is_empty() {
-        return Err(anyhow!("Cannot submit an empty answer"));
-    }
-
-    submit_answer_ext(year, day, part, answer, dry_run)
+    submit_answer_ext(year, day, part, answer, options)
 }
 
 /// Internal helper used by both `submit` and `solve`.
 ///
 /// This always uses the provided `answer` string and does not prompt for input.
+/// Every attempt, dry runs included, is recorded in the year's answer ledger.
+/// Returns the site's verdict, or `None` for a dry run.
+///
+/// The answer is trimmed, then checked with [`validate::check_answer`]:
+/// malformed values are refused and suspicious ones need confirmation on
+/// stdin, except in a dry run, which only reports them.
+/// Answers the ledger shows to be wrong are refused before anything is sent,
+/// since every wrong guess adds to the lockout. `force` skips the
+/// confirmation and sends known-wrong answers anyway.
+///
+/// When the site rate-limits the submission, `wait` counts down the time it
+/// asks for and submits again; otherwise the time a retry is allowed is
+/// printed and the rate-limited verdict returned.
+///
+/// Accepted parts are recorded as split times. Once part one is accepted,
+/// the newly revealed part two is printed and its examples saved as
+/// `dNN.example{n}.txt` next to the input.
 pub fn submit_answer_ext(
     year: u32,
     day: u8,
     part: u8,
     answer: String,
-    dry_run: bool,
-) -> Result<()> {
-    use std::io::Write as _;
+    options: SubmitOptions,
+) -> Result<Option<SubmissionVerdict>> {
+    // Send, record and compare exactly what was validated.
+    let answer = answer.trim().to_string();
+    ensure_plausible(&answer, options)?;
+
+    let mut ledger = Ledger::load(year)?;
+
+    if let Some(reason) = ledger.known_wrong(day, part, &answer) {
+        if !options.force {
+            return Err(anyhow!(
+                "Not submitting: {reason} (see `aocctl history {day}`); pass --force to \
+                 submit anyway"
+            ));
+        }
+        eprintln!("warning: {reason}; submitting anyway because of --force");
+    }
 
-    if dry_run {
+    if options.dry_run {
         println!("[dry-run] Would submit:");
         println!("  year   = {year}");
         println!("  day    = {day}");
         println!("  part   = {part}");
         println!("  answer = {answer}");
-        return Ok(());
-    }
-
-    let session = http::read_session()?;
-    let client = http::http_client()?;
-    let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
-
-    let body = serde_urlencoded::to_string(&[
-        ("level", part.to_string()),
-        ("answer", answer.clone()),
-    ])?;
-
-    println!("Submitting answer for {year} day {day} part {part}...");
-
-    let resp = client
-        .post(&url)
-        .header(reqwest::header::COOKIE, format!("session={session}"))
-        .header(
-            reqwest::header::CONTENT_TYPE,
-            "application/x-www-form-urlencoded",
-        )
-        .body(body)
-        .send()?;
-
-    let status = resp.status();
-    let text = resp.text()?;
-
-    if !status.is_success() {
-        println!("HTTP {} while submitting answer", status.as_u16());
-        println!("{text}");
-        return Ok(());
+        ledger.record(Attempt {
+            dry_run: true,
+            ..Attempt::now(day, part, &answer)
+        })?;
+        return Ok(None);
     }
 
-    if let Some(msg) = extract::extract_article_text(&text) {
+    http::ensure_online("submit an answer")?;
+
+    loop {
+        println!("Submitting answer for {year} day {day} part {part}...");
+
+        let mut attempt = Attempt::now(day, part, &answer);
+        let text = http::post_form(&format!("/{year}/day/{day}/answer"), &[
+            ("level", part.to_string()),
+            ("answer", answer.clone()),
+        ])
+        .map_err(|e| {
+            if http::is_transient(&e) {
+                // The site may have judged the answer before failing, so a
+                // blind resubmission could count twice.
+                e.context(format!(
+                    "The answer may or may not have been received; check the day page with \
+                     `aocctl --year {year} status {day}` before resubmitting"
+                ))
+            } else {
+                e
+            }
+        })?;
+
+        // The puzzle page and the calendar change once a part is accepted, so
+        // drop the cached copies.
+        http::invalidate(&http::Resource::Page { year, day })?;
+        http::invalidate(&http::Resource::Calendar { year })?;
+
+        let msg = extract::extract_article_text(&text).unwrap_or(text);
         println!("{msg}");
-    } else {
-        println!("{text}");
+        let verdict = SubmissionVerdict::parse(&msg);
+
+        attempt.verdict = Some(verdict.clone());
+        ledger.record(attempt)?;
+        println!("Recorded submission in {}", ledger.path().display());
+
+        if verdict == SubmissionVerdict::Correct {
+            hashes::record_accepted(year, day, part, &answer)?;
+            splits::record_accepted(year, day, part)?;
+
+            if part == 1
+                && let Err(e) = show_part_two(year, day)
+            {
+                eprintln!("warning: could not load part two: {e}");
+            }
+        }
+
+        let SubmissionVerdict::RateLimited { wait } = verdict else {
+            return Ok(Some(verdict));
+        };
+
+        let retry_at = SystemTime::now() + wait.unwrap_or(DEFAULT_RATE_LIMIT_WAIT);
+        if !options.wait {
+            println!(
+                "Retry allowed at {}; pass --wait to resubmit automatically",
+                calendar::format_utc(retry_at)
+            );
+            return Ok(Some(verdict));
+        }
+
+        calendar::wait_until(retry_at, "Resubmitting")?;
     }
+}
+
+/// Print the part two description that accepting part one revealed, and
+/// save its new example blocks next to the input.
+fn show_part_two(year: u32, day: u8) -> Result<()> {
+    let page = http::fetch(&http::Resource::Page { year, day })?;
+    let articles = extract::extract_articles(&page);
+    let [part_one, part_two, ..] = articles[..] else {
+        return Err(anyhow!("the puzzle page does not show part two yet"));
+    };
 
-    // Append an entry to a per-day answers log for your own notes.
-    let answers_dir = Path::new("answers").join(year.to_string());
-    fs::create_dir_all(&answers_dir)?;
-    let file = answers_dir.join(format!("d{day:02}.txt"));
+    println!();
+    println!("{}", extract::render_article(part_two));
+    println!();
 
-    let mut f = OpenOptions::new().create(true).append(true).open(&file)?;
-    writeln!(f, "part {part}: {answer}")?;
+    // Part two often repeats part one's example with a different question.
+    let seen = extract::extract_code_blocks(part_one);
+    let blocks: Vec<String> = extract::extract_code_blocks(part_two)
+        .into_iter()
+        .filter(|block| !seen.contains(block))
+        .collect();
 
-    println!("Recorded submission in {}", file.display());
+    for path in input::save_examples(year, day, &blocks)? {
+        println!("Saved example to {}", path.display());
+    }
     Ok(())
 }
+
+/// Refuse malformed answers and ask before sending suspicious ones.
+///
+/// A dry run only reports suspicious answers. When `options.unattended` is
+/// set there is nobody to ask, so they are refused unless `options.force` is
+/// set.
+fn ensure_plausible(answer: &str, options: SubmitOptions) -> Result<()> {
+    let reason = match validate::check_answer(answer) {
+        AnswerCheck::Ok => return Ok(()),
+        AnswerCheck::Invalid(reason) => {
+            return Err(anyhow!("Refusing to submit {answer:?}: {reason}"));
+        }
+        AnswerCheck::Suspicious(reason) => reason,
+    };
+
+    if options.force {
+        eprintln!("warning: {reason}; submitting anyway because of --force");
+        return Ok(());
+    }
+    if options.dry_run {
+        eprintln!("warning: {reason}; a real submission would need confirmation or --force");
+        return Ok(());
+    }
+    if options.unattended {
+        return Err(anyhow!(
+            "Not submitting suspicious answer {answer}: {reason}; pass --force"
+        ));
+    }
+
+    eprintln!("warning: {reason}");
+    println!("Submit {answer} anyway? Type `yes` to confirm:");
+    let mut buf = String::new();
+    io::stdin().read_line(&mut buf)?;
+
+    if matches!(buf.trim().to_ascii_lowercase().as_str(), "y" | "yes") {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Not submitting {answer}; confirm with `yes` or pass --force"
+        ))
+    }
+}
@@ -0,0 +1,140 @@
+//! Implementation of the `sync` subcommand (bulk download for a year).
+
+use std::time::SystemTime;
+
+use crate::commands::input::{self, Download};
+use crate::prelude::*;
+use crate::utils::profile::Profile;
+use crate::utils::response::AocError;
+use crate::utils::{calendar, http};
+
+/// Per-kind tally of what happened to each day.
+#[derive(Debug, Default)]
+struct Tally {
+    fetched: Vec<u8>,
+    skipped: Vec<u8>,
+    locked:  Vec<u8>,
+    failed:  Vec<(u8, String)>,
+}
+
+impl Tally {
+    /// Record the outcome for `day`, returning a short description.
+    ///
+    /// Errors that would repeat for every remaining day, such as a rejected
+    /// session, are passed back to the caller to abort the sync.
+    fn record(&mut self, day: u8, outcome: Result<Download>) -> Result<String> {
+        match outcome {
+            Ok(Download::Saved(path)) => {
+                self.fetched.push(day);
+                Ok(format!("fetched -> {}", path.display()))
+            }
+            Ok(Download::Exists(_)) => {
+                self.skipped.push(day);
+                Ok("already present".to_string())
+            }
+            Err(e) => match e.downcast_ref::<AocError>() {
+                Some(AocError::NotUnlocked) => {
+                    self.locked.push(day);
+                    Ok("not unlocked yet".to_string())
+                }
+                Some(AocError::InvalidSession { .. } | AocError::LoginPage) => Err(e),
+                _ => {
+                    let reason = e.to_string().lines().next().unwrap_or_default().to_string();
+                    self.failed.push((day, reason.clone()));
+                    Ok(format!("failed: {reason}"))
+                }
+            },
+        }
+    }
+
+    fn summary(&self, what: &str) -> String {
+        let days = |v: &[u8]| {
+            if v.is_empty() {
+                String::new()
+            } else {
+                let list: Vec<String> = v.iter().map(u8::to_string).collect();
+                format!(" (days {})", list.join(", "))
+            }
+        };
+        let failed: Vec<u8> = self.failed.iter().map(|(d, _)| *d).collect();
+
+        format!(
+            "{what}: {} fetched, {} skipped, {} locked{}, {} failed{}",
+            self.fetched.len(),
+            self.skipped.len(),
+            self.locked.len(),
+            days(&self.locked),
+            self.failed.len(),
+            days(&failed)
+        )
+    }
+}
+
+/// Fetch every available input for `year`, and with `pages` also each day's
+/// puzzle page.
+///
+/// Existing files are skipped, days that have not unlocked yet are reported
+/// as locked, and every request goes through the usual cache and throttle.
+pub fn sync_year(year: u32, pages: bool) -> Result<()> {
+    let days = calendar::days_in_event(year);
+    let now = SystemTime::now();
+
+    let mut inputs = Tally::default();
+    let mut puzzles = Tally::default();
+
+    println!("Syncing {year} ({days} days)...");
+
+    for day in 1..=days {
+        let progress = format!("[{day:>2}/{days}] Day {day:>2}");
+
+        if !calendar::is_unlocked(year, day, now) {
+            inputs.locked.push(day);
+            if pages {
+                puzzles.locked.push(day);
+            }
+            println!(
+                "{progress}: locked until {}",
+                calendar::format_utc(calendar::unlock_time(year, day))
+            );
+            continue;
+        }
+
+        let status = inputs.record(day, input::save_input(year, day, false, false))?;
+        println!("{progress}: input {status}");
+
+        if pages {
+            let status = puzzles.record(day, save_page(year, day))?;
+            println!("{progress}: page {status}");
+        }
+    }
+
+    println!();
+    println!("{}", inputs.summary("Inputs"));
+    if pages {
+        println!("{}", puzzles.summary("Pages"));
+    }
+
+    for (day, reason) in inputs.failed.iter().chain(&puzzles.failed) {
+        println!("  day {day}: {reason}");
+    }
+
+    Ok(())
+}
+
+/// Save the puzzle page for `year`/`day` next to its input as `dNN.html`.
+fn save_page(year: u32, day: u8) -> Result<Download> {
+    let path = Profile::active()
+        .inputs_dir(year)
+        .join(format!("d{day:02}.html"));
+    if path.exists() {
+        return Ok(Download::Exists(path));
+    }
+
+    let body = http::fetch(&http::Resource::Page { year, day })?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, body)?;
+
+    Ok(Download::Saved(path))
+}
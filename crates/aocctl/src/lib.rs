@@ -7,6 +7,8 @@ mod prelude;
 mod templates;
 mod utils;
 
+use std::process::ExitCode;
+
 use anyhow::{Result, anyhow};
 use clap::{Parser, Subcommand};
 
@@ -18,68 +20,152 @@ pub struct Cli {
     #[arg(long, global = true)]
     year: Option<u32>,
 
+    /// Never contact adventofcode.com; serve everything from the local cache.
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Named account profile with its own session, inputs and answers.
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     /// Concrete command the user wants to run.
     #[command(subcommand)]
     pub command: Command,
 }
 
 impl Cli {
-    pub fn execute() -> Result<()> {
+    /// Parse the command line and run the chosen command.
+    ///
+    /// `submit` and `solve` exit with a status that encodes the verdict; see
+    /// the README for the list. Everything else exits with `0` on success.
+    pub fn execute() -> Result<ExitCode> {
         let cli = Self::parse();
+        utils::http::set_offline(cli.offline);
+        utils::profile::Profile::activate(cli.profile.as_deref())?;
 
         // Resolve year up front for all commands that need it.
         // `Init` uses its own explicit year.
-        match cli.command {
-            Command::Init { year } => commands::init::init_year(year),
+        let verdict = match cli.command {
+            Command::Init { year } => commands::init::init_year(year).map(|()| None),
             Command::New { day, template } => {
                 let year = utils::detect::resolve_year(cli.year)?;
-                commands::new::new_day(year, day, template)
+                commands::new::new_day(year, day, template).map(|()| None)
             }
             Command::Open { day } => {
                 let year = utils::detect::resolve_year(cli.year)?;
-                commands::open::open_day(year, day)
+                commands::open::open_day(year, day).map(|()| None)
             }
-            Command::Input { day } => {
+            Command::Input { day, force, wait } => {
                 let year = utils::detect::resolve_year(cli.year)?;
-                commands::input::download_input(year, day)
+                commands::input::download_input(year, day, force, wait).map(|()| None)
             }
             Command::Run { day, input, mode } => {
                 let year = utils::detect::resolve_year(cli.year)?;
-                commands::run::run_day(year, day, &input, mode)
+                commands::run::run_day(year, day, &input, mode).map(|()| None)
             }
             Command::Current { input, mode } => {
                 let year = utils::detect::resolve_year(cli.year)?;
                 let day = utils::detect::detect_latest_day(year)?
                     .ok_or_else(|| anyhow!("No days found for year {year}"))?;
-                commands::run::run_day(year, day, &input, mode)
+                commands::run::run_day(year, day, &input, mode).map(|()| None)
             }
             Command::Submit {
                 day,
                 part,
                 answer,
                 dry_run,
+                force,
+                wait,
             } => {
                 let year = utils::detect::resolve_year(cli.year)?;
-                commands::submit::submit_answer(year, day, part, answer, dry_run)
+                let options = commands::SubmitOptions {
+                    dry_run,
+                    force,
+                    wait,
+                    unattended: false,
+                };
+                commands::submit::submit_answer(year, day, part, answer, options)
             }
             Command::Solve {
                 day,
                 input,
                 mode,
+                auto,
                 dry_run,
+                force,
+                wait,
             } => {
                 let year = utils::detect::resolve_year(cli.year)?;
-                commands::solve::solve_day(year, day, &input, mode, dry_run)
+                let options = commands::SubmitOptions {
+                    dry_run,
+                    force,
+                    wait,
+                    unattended: auto,
+                };
+                commands::solve::solve_day(year, day, &input, mode, auto, options)
+            }
+            Command::Status {
+                all_years: true,
+                json,
+                ..
+            } => commands::status::check_all_years(json).map(|()| None),
+            Command::Status { day, json, .. } => {
+                let year = utils::detect::resolve_year(cli.year)?;
+                commands::status::check_status(year, day, json).map(|()| None)
             }
-            Command::Status { day } => {
+            Command::History { day } => {
                 let year = utils::detect::resolve_year(cli.year)?;
-                commands::status::check_status(year, day)
+                commands::history::show_history(year, day).map(|()| None)
+            }
+            Command::Splits { year } => {
+                let year = utils::detect::resolve_year(year.or(cli.year))?;
+                commands::splits::show_splits(year).map(|()| None)
+            }
+            Command::Sync { year, pages } => {
+                let year = utils::detect::resolve_year(year.or(cli.year))?;
+                commands::sync::sync_year(year, pages).map(|()| None)
+            }
+            Command::Answers { action } => match action {
+                AnswersCommand::Pull { year, days } => {
+                    let year = utils::detect::resolve_year(year.or(cli.year))?;
+                    commands::answers::pull_answers(year, days)
+                }
+            }
+            .map(|()| None),
+            Command::Auth { action } => match action {
+                AuthCommand::Set { token } => commands::auth::set_token(token),
+                AuthCommand::Show => commands::auth::show_token(),
+                AuthCommand::Check => commands::auth::check_token(),
+                AuthCommand::Import { browser, cookies } => {
+                    commands::auth::import_token(browser, cookies)
+                }
+            }
+            .map(|()| None),
+            Command::Leaderboard {
+                id,
+                file,
+                watch,
+                stats,
+                csv,
+                exclude_days,
+            } => {
+                let file = file.as_deref();
+                let shown = if watch {
+                    commands::leaderboard::watch_leaderboard(cli.year, id, file)
+                } else if stats {
+                    commands::leaderboard::show_stats(cli.year, id, file, &exclude_days, csv)
+                } else {
+                    commands::leaderboard::show_leaderboard(cli.year, id, file)
+                };
+                shown.map(|()| None)
             }
             Command::ListTemplates => {
                 commands::new::list_templates();
-                Ok(())
+                Ok(None)
             }
-        }
+        }?;
+
+        Ok(verdict.map_or(ExitCode::SUCCESS, |v| ExitCode::from(v.exit_code())))
     }
 }
 
@@ -125,6 +211,14 @@ pub enum Command {
     Input {
         /// Day index in the range 1-25.
         day: u8,
+
+        /// Download again even if the input file already exists.
+        #[arg(long)]
+        force: bool,
+
+        /// Count down to the unlock instant (midnight UTC-5), then download.
+        #[arg(long)]
+        wait: bool,
     },
 
     /// Build and run a specific day.
@@ -167,19 +261,55 @@ pub enum Command {
         /// Print what would be submitted without contacting the AoC server.
         #[arg(long)]
         dry_run: bool,
+
+        /// Submit even if earlier verdicts show the answer is wrong, and
+        /// suspicious answers such as `0` without asking.
+        #[arg(long)]
+        force: bool,
+
+        /// When rate-limited, count down and resubmit automatically.
+        #[arg(long)]
+        wait: bool,
     },
 
-    /// Show how many stars you have for a particular day.
+    /// Show your stars for a day, or a calendar of the whole year.
     Status {
-        /// Day index in the range 1-25.
-        day: u8,
+        /// Day index in the range 1-25. Without it, the whole year's calendar
+        /// is shown.
+        day: Option<u8>,
+
+        /// Show every event's stars next to the local `crates/aocYYYY` crates
+        /// and list days where the two disagree.
+        #[arg(long, conflicts_with = "day")]
+        all_years: bool,
+
+        /// Print the stars as JSON for scripts.
+        #[arg(long)]
+        json: bool,
     },
+
+    /// List every answer submitted for the year, with its time and verdict.
+    History {
+        /// Only show answers for this day.
+        day: Option<u8>,
+    },
+
+    /// Show how long each star took, measured from the puzzle unlock.
+    ///
+    /// Times are recorded when an input is first downloaded and when a part
+    /// is accepted, but only during the event's December.
+    Splits {
+        /// Year to show, for example `2024`. Defaults to the current year.
+        year: Option<u32>,
+    },
+
     /// Run the solver and interactively submit an answer.
     ///
     /// This command:
     ///   * builds and runs the day binary,
     ///   * parses its output for `Part 1:` and `Part 2:` lines,
-    ///   * prompts the user which part to submit,
+    ///   * prompts the user which part to submit, or with `--auto` picks the
+    ///     first part the day page does not show as solved,
     ///   * and then calls the submission logic.
     Solve {
         /// Day index in the range 1-25.
@@ -191,12 +321,125 @@ pub enum Command {
 
         /// Build mode: debug or release.
         #[arg(long, value_enum, default_value_t = commands::RunMode::Release)]
-        mode:    commands::RunMode,
+        mode: commands::RunMode,
+
+        /// Submit the first unsolved part without prompting.
+        #[arg(long)]
+        auto: bool,
+
         /// Print what would be submitted without contacting the AoC server.
         #[arg(long)]
         dry_run: bool,
+
+        /// Submit even if earlier verdicts show the answer is wrong, and
+        /// suspicious answers such as `0` without asking.
+        #[arg(long)]
+        force: bool,
+
+        /// When rate-limited, count down and resubmit automatically.
+        #[arg(long)]
+        wait: bool,
+    },
+
+    /// Download every available input for a year, skipping existing files.
+    ///
+    /// Prints progress per day and a summary of what was fetched, skipped or
+    /// is still locked.
+    Sync {
+        /// Year to sync, for example `2024`. Defaults to the current year.
+        year: Option<u32>,
+
+        /// Also save each day's puzzle page as `dNN.html` next to the input.
+        #[arg(long)]
+        pages: bool,
+    },
+
+    /// Work with the answers recorded in the answer ledger.
+    Answers {
+        #[command(subcommand)]
+        action: AnswersCommand,
+    },
+
+    /// Manage the Advent of Code session token.
+    Auth {
+        #[command(subcommand)]
+        action: AuthCommand,
+    },
+
+    /// Show a private leaderboard with each member's score, stars and
+    /// progress per day.
+    ///
+    /// Downloads are cached for 15 minutes, as the site asks.
+    Leaderboard {
+        /// Leaderboard id, the number in its URL.
+        id: u64,
+
+        /// Read the leaderboard JSON from this file instead of the site.
+        #[arg(long)]
+        file: Option<std::path::PathBuf>,
+
+        /// Keep polling every 15 minutes and print each new star. Set
+        /// `leaderboard_webhook` in the config to also POST them as JSON.
+        #[arg(long, conflicts_with = "stats")]
+        watch: bool,
+
+        /// Show each member's time from unlock to every star, recomputed
+        /// scores and their rank after each day.
+        #[arg(long)]
+        stats: bool,
+
+        /// With `--stats`, print one CSV row per member and day instead.
+        #[arg(long, requires = "stats")]
+        csv: bool,
+
+        /// With `--stats`, leave these days out of the recomputed scores, for
+        /// example `--exclude-days 1,6`.
+        #[arg(long, value_delimiter = ',', requires = "stats")]
+        exclude_days: Vec<u8>,
     },
 
     /// List all available templates and their descriptions.
     ListTemplates,
 }
+
+/// Subcommands of `aocctl answers`.
+#[derive(Debug, Clone, Subcommand)]
+pub enum AnswersCommand {
+    /// Record the answers the site already accepted, read from the puzzle
+    /// pages, as known-correct in the ledger.
+    Pull {
+        /// Year to pull, for example `2024`. Defaults to the current year.
+        year: Option<u32>,
+
+        /// A single day, or `all`.
+        #[arg(default_value = "all")]
+        days: commands::Days,
+    },
+}
+
+/// Subcommands of `aocctl auth`.
+#[derive(Debug, Clone, Subcommand)]
+pub enum AuthCommand {
+    /// Store a session token in `.session` with owner-only permissions.
+    Set {
+        /// Token to store. If omitted, read from standard input.
+        token: Option<String>,
+    },
+
+    /// Show where the session token comes from, with the token redacted.
+    Show,
+
+    /// Check that the session token is accepted and print the user name.
+    Check,
+
+    /// Copy the session cookie out of a local browser profile into `.session`.
+    Import {
+        /// Browser whose default profile holds the cookie.
+        #[arg(long, value_enum)]
+        browser: commands::Browser,
+
+        /// Read this cookie database instead of the default profile's.
+        #[arg(long)]
+        cookies: Option<std::path::PathBuf>,
+    },
+}
@@ -1,4 +1,6 @@
 //! The binary entry point for the `aocctl` command line tool.
+use std::process::ExitCode;
+
 use aocctl::Cli;
 
-fn main() -> anyhow::Result<()> { Cli::execute() }
+fn main() -> anyhow::Result<ExitCode> { Cli::execute() }
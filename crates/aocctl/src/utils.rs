@@ -1,6 +1,19 @@
 //! Utility modules that support the command implementations.
 
+pub mod cache;
+pub mod calendar;
+pub mod config;
+pub mod cookies;
 pub mod detect;
 pub mod extract;
+pub mod hashes;
 pub mod http;
 pub mod input;
+pub mod leaderboard;
+pub mod ledger;
+pub mod profile;
+pub mod response;
+pub mod splits;
+pub mod throttle;
+pub mod validate;
+pub mod verdict;
@@ -0,0 +1,168 @@
+//! On-disk cache for pages and inputs fetched from Advent of Code.
+//!
+//! Entries live under `.aocctl/cache/{year}/{day}/...`, or below the profile's
+//! state directory when `--profile` is used. Sites other than the real one,
+//! such as a mock server, get their own `cache/sites/{host}/` directory so
+//! their responses are never served for the real site. Every entry is stored
+//! next to a small `.fetched` sidecar that records when it was downloaded, so
+//! callers can decide whether a cached copy is still fresh enough to use.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::prelude::*;
+use crate::utils::config;
+use crate::utils::profile::Profile;
+
+/// A cached response body together with the time it was fetched.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub body:       String,
+    pub fetched_at: SystemTime,
+}
+
+impl Entry {
+    /// How long ago this entry was fetched.
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.fetched_at)
+            .unwrap_or_default()
+    }
+
+    /// Whether the entry is younger than `max_age`. `None` never expires.
+    pub fn is_fresh(&self, max_age: Option<Duration>) -> bool {
+        max_age.is_none_or(|max| self.age() <= max)
+    }
+}
+
+/// Handle to the on-disk cache directory.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    root: PathBuf,
+}
+
+impl Cache {
+    /// Open the cache of the active profile for the site at `base_url`,
+    /// `.aocctl/cache` by default.
+    pub fn open(base_url: &str) -> Self {
+        let root = Profile::active().state_dir().join("cache");
+        if base_url == config::DEFAULT_BASE_URL {
+            Self::at(root)
+        } else {
+            Self::at(root.join("sites").join(site_dir(base_url)))
+        }
+    }
+
+    /// Open a cache rooted at an arbitrary directory.
+    pub fn at(root: impl Into<PathBuf>) -> Self { Self { root: root.into() } }
+
+    /// Look up an entry by its relative key, e.g. `2025/05/input.txt`.
+    pub fn get(&self, key: impl AsRef<Path>) -> Result<Option<Entry>> {
+        let path = self.root.join(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let body = fs::read_to_string(&path)?;
+        let fetched_at = fs::read_to_string(sidecar(&path))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap_or(UNIX_EPOCH);
+
+        Ok(Some(Entry { body, fetched_at }))
+    }
+
+    /// Store `body` under `key`, stamping it with the current time.
+    pub fn put(&self, key: impl AsRef<Path>, body: &str) -> Result<()> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        fs::write(&path, body)?;
+        fs::write(sidecar(&path), now.to_string())?;
+        Ok(())
+    }
+
+    /// Drop an entry so the next lookup goes back to the network.
+    pub fn remove(&self, key: impl AsRef<Path>) -> Result<()> {
+        let path = self.root.join(key);
+        for p in [sidecar(&path), path] {
+            if p.exists() {
+                fs::remove_file(p)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Directory name for the site at `base_url`, e.g. `127.0.0.1_8080`.
+fn site_dir(base_url: &str) -> String {
+    let host = base_url
+        .split_once("://")
+        .map_or(base_url, |(_, rest)| rest);
+    host.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-') { c } else { '_' })
+        .collect()
+}
+
+fn sidecar(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".fetched");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn put_then_get_round_trips_body_and_timestamp() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::at(dir.path());
+
+        cache.put("2025/05/input.txt", "1\n2\n3").unwrap();
+        let entry = cache.get("2025/05/input.txt").unwrap().unwrap();
+
+        assert_eq!(entry.body, "1\n2\n3");
+        assert!(entry.age() < Duration::from_secs(60));
+        assert!(entry.is_fresh(None));
+        assert!(entry.is_fresh(Some(Duration::from_secs(60))));
+    }
+
+    #[test]
+    fn missing_sidecar_counts_as_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::at(dir.path());
+
+        cache.put("2025/01/page.html", "<html>").unwrap();
+        fs::remove_file(dir.path().join("2025/01/page.html.fetched")).unwrap();
+
+        let entry = cache.get("2025/01/page.html").unwrap().unwrap();
+        assert!(!entry.is_fresh(Some(Duration::from_secs(3600))));
+        assert!(entry.is_fresh(None));
+    }
+
+    #[test]
+    fn other_sites_get_their_own_directory() {
+        assert_eq!(site_dir("http://127.0.0.1:8080"), "127.0.0.1_8080");
+        assert_eq!(site_dir("https://aoc.example/mirror"), "aoc.example_mirror");
+
+        let real = Cache::open(config::DEFAULT_BASE_URL);
+        let mock = Cache::open("http://127.0.0.1:8080");
+        assert_ne!(real.root, mock.root);
+        assert!(mock.root.starts_with(&real.root));
+    }
+
+    #[test]
+    fn remove_drops_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::at(dir.path());
+
+        cache.put("2025/01/page.html", "<html>").unwrap();
+        cache.remove("2025/01/page.html").unwrap();
+
+        assert!(cache.get("2025/01/page.html").unwrap().is_none());
+    }
+}
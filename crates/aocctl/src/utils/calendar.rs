@@ -0,0 +1,188 @@
+//! Advent of Code calendar arithmetic.
+//!
+//! Puzzles unlock at midnight US Eastern Standard Time (UTC-5) on each day of
+//! December. All calculations here are done in UTC with plain integer math,
+//! so they do not depend on the local time zone.
+
+use std::io::{IsTerminal as _, Write as _};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::prelude::*;
+
+/// Seconds after midnight UTC at which puzzles unlock (midnight UTC-5).
+const UNLOCK_OFFSET_SECS: u64 = 5 * 60 * 60;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Number of puzzles in the event for `year`.
+///
+/// Events up to 2024 had 25 days; from 2025 on there are 12.
+pub const fn days_in_event(year: u32) -> u8 { if year >= 2025 { 12 } else { 25 } }
+
+/// Check that `day` exists in the event for `year`.
+pub fn validate_day(year: u32, day: u8) -> Result<()> {
+    let last = days_in_event(year);
+    if day == 0 || day > last {
+        return Err(anyhow!(
+            "Day must be between 1 and {last} for {year}, got {day}"
+        ));
+    }
+    Ok(())
+}
+
+/// The instant the puzzle for `year`/`day` unlocks.
+pub fn unlock_time(year: u32, day: u8) -> SystemTime {
+    let days = days_from_civil(i64::from(year), 12, u32::from(day));
+    let secs = days as u64 * SECS_PER_DAY + UNLOCK_OFFSET_SECS;
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+/// Whether the puzzle for `year`/`day` is unlocked at `now`.
+pub fn is_unlocked(year: u32, day: u8, now: SystemTime) -> bool {
+    now >= unlock_time(year, day)
+}
+
+/// Whether `now` falls in December of `year`'s event, from the first unlock
+/// until midnight UTC-5 on New Year's Day.
+pub fn is_during_event(year: u32, now: SystemTime) -> bool {
+    let start = unlock_time(year, 1);
+    (start..start + Duration::from_secs(31 * SECS_PER_DAY)).contains(&now)
+}
+
+/// Format an instant as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn format_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (y, m, d) = civil_from_days((secs / SECS_PER_DAY) as i64);
+    let rem = secs % SECS_PER_DAY;
+    format!(
+        "{y:04}-{m:02}-{d:02} {:02}:{:02}:{:02} UTC",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Format a duration as `[Nd ]HH:MM:SS`.
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    let days = secs / SECS_PER_DAY;
+    let rem = secs % SECS_PER_DAY;
+    let clock = format!("{:02}:{:02}:{:02}", rem / 3600, rem % 3600 / 60, rem % 60);
+    if days > 0 { format!("{days}d {clock}") } else { clock }
+}
+
+/// Block until `until`, showing a live countdown prefixed with `label`.
+///
+/// On a terminal the countdown is redrawn in place every second; otherwise a
+/// single line is printed so logs stay readable.
+pub fn wait_until(until: SystemTime, label: &str) -> Result<()> {
+    let remaining = || until.duration_since(SystemTime::now()).unwrap_or_default();
+
+    if !io::stdout().is_terminal() {
+        println!("{label} in {}", format_duration(remaining()));
+        thread::sleep(remaining());
+        return Ok(());
+    }
+
+    let mut out = io::stdout();
+    while !remaining().is_zero() {
+        // Round up so the display reaches 00:00:00 as the wait ends.
+        let left = Duration::from_secs(remaining().as_secs_f64().ceil() as u64);
+        write!(out, "\r{label} in {}   ", format_duration(left))?;
+        out.flush()?;
+        thread::sleep(remaining().min(Duration::from_secs(1)));
+    }
+    writeln!(out, "\r{label} now{}", " ".repeat(16))?;
+    Ok(())
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+///
+/// Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = i64::from(m);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of [`days_from_civil`].
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(t: SystemTime) -> u64 { t.duration_since(UNIX_EPOCH).unwrap().as_secs() }
+
+    #[test]
+    fn unlock_is_five_am_utc_on_the_day() {
+        assert_eq!(secs(unlock_time(2015, 1)), 1_448_946_000);
+        assert_eq!(secs(unlock_time(2020, 7)), 1_607_317_200);
+        assert_eq!(secs(unlock_time(2024, 25)), 1_735_102_800);
+        assert_eq!(secs(unlock_time(2025, 1)), 1_764_565_200);
+    }
+
+    #[test]
+    fn unlocked_exactly_at_the_instant() {
+        let t = unlock_time(2025, 3);
+        assert!(is_unlocked(2025, 3, t));
+        assert!(!is_unlocked(2025, 3, t - Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn event_runs_through_december() {
+        let start = unlock_time(2025, 1);
+        assert!(is_during_event(2025, start));
+        assert!(is_during_event(2025, unlock_time(2025, 31)));
+        assert!(!is_during_event(2025, start - Duration::from_secs(1)));
+        assert!(!is_during_event(
+            2025,
+            unlock_time(2026, 1) - Duration::from_secs(1)
+        ));
+        assert!(!is_during_event(2024, start));
+    }
+
+    #[test]
+    fn civil_round_trip() {
+        for days in [-1, 0, 59, 365, 10_957, 20_423, 40_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn formats_instants_and_durations() {
+        let t = UNIX_EPOCH + Duration::from_secs(1_764_565_200 + 3723);
+        assert_eq!(format_utc(t), "2025-12-01 06:02:03 UTC");
+        assert_eq!(format_duration(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(format_duration(Duration::from_secs(90_061)), "1d 01:01:01");
+    }
+
+    #[test]
+    fn event_length_and_day_validation() {
+        assert_eq!(days_in_event(2024), 25);
+        assert_eq!(days_in_event(2025), 12);
+        assert!(validate_day(2024, 25).is_ok());
+        assert!(validate_day(2025, 13).is_err());
+        assert!(validate_day(2025, 0).is_err());
+    }
+}
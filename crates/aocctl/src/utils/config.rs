@@ -0,0 +1,73 @@
+//! Optional project configuration stored in `.aocctl/config.toml`.
+//!
+//! Every field is optional; a missing file behaves like an empty one.
+//!
+//! ```toml
+//! base_url = "http://127.0.0.1:8080"
+//! request_gap_secs = 5
+//! hash_answers = true
+//! leaderboard_webhook = "http://127.0.0.1:9000/aoc"
+//! ```
+
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+/// Site used when neither `AOC_BASE_URL` nor `base_url` is set.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings read from `.aocctl/config.toml`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Root URL of the Advent of Code site, without a trailing slash.
+    pub base_url: Option<String>,
+
+    /// Minimum number of seconds between two requests to the site.
+    pub request_gap_secs: Option<u64>,
+
+    /// Also keep salted hashes of accepted answers in `answer-hashes/`, and
+    /// compare outputs against those instead of the plain-text answers.
+    pub hash_answers: bool,
+
+    /// PBKDF2 rounds for new answer hash files; 600 000 when not set.
+    pub hash_iterations: Option<u32>,
+
+    /// URL that `leaderboard --watch` POSTs new stars to as JSON.
+    pub leaderboard_webhook: Option<String>,
+}
+
+impl Config {
+    /// Location of the config file relative to the workspace root.
+    pub fn path() -> PathBuf { Path::new(".aocctl").join("config.toml") }
+
+    /// Load the config file, or the defaults if it does not exist.
+    pub fn load() -> Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = fs::read_to_string(&path)?;
+        toml::from_str(&text).map_err(|e| anyhow!("Invalid config in {}: {e}", path.display()))
+    }
+}
+
+/// Resolve the Advent of Code base URL.
+///
+/// The `AOC_BASE_URL` environment variable wins over the `base_url` config
+/// entry, which in turn wins over [`DEFAULT_BASE_URL`].
+pub fn base_url() -> Result<String> {
+    let from_env = std::env::var("AOC_BASE_URL")
+        .ok()
+        .filter(|v| !v.trim().is_empty());
+
+    let url = match from_env {
+        Some(url) => url,
+        None => Config::load()?
+            .base_url
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+    };
+
+    Ok(url.trim().trim_end_matches('/').to_string())
+}
@@ -0,0 +1,387 @@
+//! Read the Advent of Code `session` cookie from a local browser profile.
+//!
+//! Supported on Linux:
+//!   * Firefox: `cookies.sqlite` in the default profile from `profiles.ini`,
+//!   * Chromium and Chrome: the `Cookies` database of the `Default` profile,
+//!     when the value is stored unencrypted or with the basic `v10` scheme.
+//!
+//! Browsers keep their cookie database locked while running, so it is copied
+//! (together with any write-ahead log) to a temporary directory first.
+
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockDecryptMut as _, KeyIvInit as _};
+use rusqlite::{Connection, OpenFlags, OptionalExtension as _};
+use sha1::Sha1;
+
+use crate::prelude::*;
+
+/// Cookie hosts the session may be stored under.
+const HOSTS: [&str; 2] = [".adventofcode.com", "adventofcode.com"];
+
+/// Password Chromium uses on Linux when no keyring is available.
+const CHROMIUM_PASSWORD: &[u8] = b"peanuts";
+const CHROMIUM_SALT: &[u8] = b"saltysalt";
+
+/// Cookie databases from this version on prefix values with a host digest.
+const CHROMIUM_HASHED_HOST_VERSION: i64 = 24;
+
+/// Locate Firefox's default profile directory below `home`.
+pub fn firefox_profile(home: &Path) -> Result<PathBuf> {
+    let roots = [
+        home.join(".mozilla/firefox"),
+        home.join("snap/firefox/common/.mozilla/firefox"),
+    ];
+
+    for root in &roots {
+        let ini = root.join("profiles.ini");
+        if let Ok(text) = fs::read_to_string(&ini)
+            && let Some(profile) = default_profile_in_ini(&text)
+        {
+            return Ok(root.join(profile));
+        }
+    }
+
+    Err(anyhow!(
+        "No Firefox profile found; looked for profiles.ini in {}",
+        roots
+            .iter()
+            .map(|r| r.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" and ")
+    ))
+}
+
+/// Pick the default profile path out of a Firefox `profiles.ini`.
+///
+/// An `[Install…]` section's `Default=` wins, then a `[Profile…]` section
+/// marked `Default=1`, then the first profile listed.
+fn default_profile_in_ini(text: &str) -> Option<String> {
+    let mut install = None;
+    let mut marked = None;
+    let mut first = None;
+
+    let mut section = String::new();
+    let mut path = None;
+    let mut is_default = false;
+
+    let mut close = |section: &str, path: &mut Option<String>, is_default: &mut bool| {
+        if section.starts_with("Profile")
+            && let Some(p) = path.take()
+        {
+            if *is_default && marked.is_none() {
+                marked = Some(p.clone());
+            }
+            first.get_or_insert(p);
+        }
+        *is_default = false;
+        path.take();
+    };
+
+    for line in text.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            close(&section, &mut path, &mut is_default);
+            section = name.to_string();
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match (key.trim(), section.starts_with("Install")) {
+            ("Default", true) => {
+                install.get_or_insert_with(|| value.trim().to_string());
+            }
+            ("Default", false) => is_default = value.trim() == "1",
+            ("Path", false) => path = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    close(&section, &mut path, &mut is_default);
+
+    install.or(marked).or(first)
+}
+
+/// Locate the cookie database of the default Chromium or Chrome profile.
+pub fn chromium_cookie_db(home: &Path) -> Result<PathBuf> {
+    let candidates: Vec<PathBuf> = ["chromium", "google-chrome"]
+        .iter()
+        .flat_map(|browser| {
+            let profile = home.join(".config").join(browser).join("Default");
+            [profile.join("Network/Cookies"), profile.join("Cookies")]
+        })
+        .collect();
+
+    candidates
+        .iter()
+        .find(|p| p.exists())
+        .cloned()
+        .ok_or_else(|| {
+            anyhow!(
+                "No Chromium cookie database found; looked for {}",
+                candidates
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+/// Read the session cookie from a Firefox `cookies.sqlite` database.
+pub fn read_firefox_session(db: &Path) -> Result<Option<String>> {
+    with_copy(db, |conn| {
+        let value = conn
+            .query_row(
+                "SELECT value FROM moz_cookies WHERE name = 'session' AND host IN (?1, ?2) \
+                 ORDER BY expiry DESC LIMIT 1",
+                HOSTS,
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+        Ok(value.filter(|v| !v.is_empty()))
+    })
+}
+
+/// Read the session cookie from a Chromium `Cookies` database.
+pub fn read_chromium_session(db: &Path) -> Result<Option<String>> {
+    with_copy(db, |conn| {
+        let row = conn
+            .query_row(
+                "SELECT value, encrypted_value FROM cookies WHERE name = 'session' AND \
+                 host_key IN (?1, ?2) ORDER BY expires_utc DESC LIMIT 1",
+                HOSTS,
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?)),
+            )
+            .optional()?;
+
+        let Some((value, encrypted)) = row else {
+            return Ok(None);
+        };
+        if !value.is_empty() {
+            return Ok(Some(value));
+        }
+        if encrypted.is_empty() {
+            return Ok(None);
+        }
+
+        let version = conn
+            .query_row("SELECT value FROM meta WHERE key = 'version'", [], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()?
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(0);
+
+        decrypt_chromium(&encrypted, version >= CHROMIUM_HASHED_HOST_VERSION).map(Some)
+    })
+}
+
+/// Decrypt a Chromium cookie value stored with the basic Linux `v10` scheme.
+fn decrypt_chromium(encrypted: &[u8], hashed_host: bool) -> Result<String> {
+    let Some(ciphertext) = encrypted.strip_prefix(b"v10") else {
+        let prefix = String::from_utf8_lossy(&encrypted[..encrypted.len().min(3)]).into_owned();
+        return Err(anyhow!(
+            "The session cookie is encrypted with scheme {prefix:?}, which needs the desktop \
+             keyring; copy it from devtools and use `aocctl auth set` instead"
+        ));
+    };
+
+    let mut buf = ciphertext.to_vec();
+    let plain = cbc::Decryptor::<aes::Aes128>::new(&chromium_key().into(), &[b' '; 16].into())
+        .decrypt_padded_mut::<Pkcs7>(&mut buf)
+        .map_err(|_| anyhow!("Could not decrypt the Chromium session cookie"))?;
+
+    // Newer databases prepend a SHA-256 digest of the host to the value.
+    let plain = if hashed_host { plain.get(32..).unwrap_or_default() } else { plain };
+
+    String::from_utf8(plain.to_vec())
+        .map_err(|_| anyhow!("The decrypted Chromium session cookie is not valid UTF-8"))
+}
+
+fn chromium_key() -> [u8; 16] {
+    let mut key = [0; 16];
+    pbkdf2::pbkdf2_hmac::<Sha1>(CHROMIUM_PASSWORD, CHROMIUM_SALT, 1, &mut key);
+    key
+}
+
+/// Copy `db` (and its `-wal` file, if any) to a temporary directory and run
+/// `f` on a read-only connection to the copy.
+fn with_copy<T>(db: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    if !db.exists() {
+        return Err(anyhow!("Cookie database not found: {}", db.display()));
+    }
+
+    let dir = tempfile::tempdir()?;
+    let copy = dir.path().join("cookies.sqlite");
+    fs::copy(db, &copy)?;
+
+    let mut wal = db.as_os_str().to_os_string();
+    wal.push("-wal");
+    if Path::new(&wal).exists() {
+        fs::copy(&wal, dir.path().join("cookies.sqlite-wal"))?;
+    }
+
+    let conn = Connection::open_with_flags(&copy, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use aes::cipher::BlockEncryptMut as _;
+
+    use super::*;
+
+    fn firefox_db(dir: &Path, rows: &[(&str, &str, &str, i64)]) -> PathBuf {
+        let path = dir.join("cookies.sqlite");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE moz_cookies (id INTEGER PRIMARY KEY, name TEXT, value TEXT, host \
+             TEXT, expiry INTEGER);",
+        )
+        .unwrap();
+        for (host, name, value, expiry) in rows {
+            conn.execute(
+                "INSERT INTO moz_cookies (name, value, host, expiry) VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![name, value, host, expiry],
+            )
+            .unwrap();
+        }
+        path
+    }
+
+    fn chromium_db(dir: &Path, version: i64, value: &str, encrypted: &[u8]) -> PathBuf {
+        let path = dir.join("Cookies");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE meta (key TEXT, value TEXT);
+             CREATE TABLE cookies (host_key TEXT, name TEXT, value TEXT, encrypted_value BLOB, \
+             expires_utc INTEGER);",
+        )
+        .unwrap();
+        conn.execute("INSERT INTO meta VALUES ('version', ?1)", [
+            version.to_string()
+        ])
+        .unwrap();
+        conn.execute(
+            "INSERT INTO cookies VALUES ('.adventofcode.com', 'session', ?1, ?2, 1)",
+            rusqlite::params![value, encrypted],
+        )
+        .unwrap();
+        path
+    }
+
+    fn encrypt_v10(plain: &[u8]) -> Vec<u8> {
+        let mut buf = vec![0; plain.len() + 16];
+        buf[..plain.len()].copy_from_slice(plain);
+        let ct = cbc::Encryptor::<aes::Aes128>::new(&chromium_key().into(), &[b' '; 16].into())
+            .encrypt_padded_mut::<Pkcs7>(&mut buf, plain.len())
+            .unwrap();
+        [b"v10".as_slice(), ct].concat()
+    }
+
+    #[test]
+    fn firefox_picks_newest_aoc_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = firefox_db(dir.path(), &[
+            (".example.com", "session", "other-site", 9),
+            (".adventofcode.com", "session", "old", 1),
+            (".adventofcode.com", "session", "new", 2),
+            (".adventofcode.com", "_ga", "tracking", 3),
+        ]);
+
+        assert_eq!(read_firefox_session(&db).unwrap().as_deref(), Some("new"));
+    }
+
+    #[test]
+    fn firefox_without_session_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = firefox_db(dir.path(), &[(".example.com", "session", "x", 1)]);
+
+        assert_eq!(read_firefox_session(&db).unwrap(), None);
+    }
+
+    #[test]
+    fn chromium_plain_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = chromium_db(dir.path(), 18, "plain-token", b"");
+
+        assert_eq!(
+            read_chromium_session(&db).unwrap().as_deref(),
+            Some("plain-token")
+        );
+    }
+
+    #[test]
+    fn chromium_v10_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = chromium_db(dir.path(), 18, "", &encrypt_v10(b"secret-token"));
+
+        assert_eq!(
+            read_chromium_session(&db).unwrap().as_deref(),
+            Some("secret-token")
+        );
+    }
+
+    #[test]
+    fn chromium_v10_value_with_host_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = [[7u8; 32].as_slice(), b"secret-token"].concat();
+        let db = chromium_db(dir.path(), 24, "", &encrypt_v10(&plain));
+
+        assert_eq!(
+            read_chromium_session(&db).unwrap().as_deref(),
+            Some("secret-token")
+        );
+    }
+
+    #[test]
+    fn chromium_keyring_scheme_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = chromium_db(dir.path(), 18, "", b"v11abcdefabcdefabcdef");
+
+        let err = read_chromium_session(&db).unwrap_err().to_string();
+        assert!(err.contains("keyring"), "{err}");
+    }
+
+    #[test]
+    fn profiles_ini_prefers_install_default() {
+        let profiles = r"
+            [Profile1]
+            Name=default
+            IsRelative=1
+            Path=wxyz.default
+            Default=1
+
+            [Profile0]
+            Name=default-release
+            IsRelative=1
+            Path=abcd.default-release
+        ";
+        let install = r"
+            [Install4F96D1932A9F858E]
+            Default=abcd.default-release
+            Locked=1
+        ";
+
+        // Without an install section the profile marked `Default=1` wins...
+        assert_eq!(
+            default_profile_in_ini(profiles).as_deref(),
+            Some("wxyz.default")
+        );
+        // ...but the install default overrides it.
+        let ini = format!("{install}{profiles}");
+        assert_eq!(
+            default_profile_in_ini(&ini).as_deref(),
+            Some("abcd.default-release")
+        );
+    }
+
+    #[test]
+    fn profiles_ini_falls_back_to_marked_then_first() {
+        let ini = "[Profile0]\nPath=first\n\n[Profile1]\nPath=marked\nDefault=1\n";
+        assert_eq!(default_profile_in_ini(ini).as_deref(), Some("marked"));
+
+        let ini = "[General]\nStartWithLastProfile=1\n\n[Profile0]\nPath=only\n";
+        assert_eq!(default_profile_in_ini(ini).as_deref(), Some("only"));
+    }
+}
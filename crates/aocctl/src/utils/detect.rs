@@ -20,22 +20,26 @@ pub fn resolve_year(global: Option<u32>) -> Result<u32> {
 ///
 /// This looks for directories with names of the form `aocYYYY` and returns
 /// the maximum year, or `None` if none exist.
-pub fn detect_latest_year() -> Result<Option<u32>> {
+pub fn detect_latest_year() -> Result<Option<u32>> { Ok(local_years()?.last().copied()) }
+
+/// Every year with a `crates/aocYYYY` directory, in ascending order.
+pub fn local_years() -> Result<Vec<u32>> {
     let crates_dir = Path::new("crates");
     if !crates_dir.exists() {
-        return Ok(None);
+        return Ok(Vec::new());
     }
 
-    let max_year = fs::read_dir(crates_dir)?
+    let mut years: Vec<u32> = fs::read_dir(crates_dir)?
         .filter_map(|entry| entry.ok())
         .filter_map(|entry| {
             let name = entry.file_name().to_string_lossy().to_string();
             name.strip_prefix("aoc").map(str::to_string)
         })
         .filter_map(|suffix| suffix.parse::<u32>().ok())
-        .max();
+        .collect();
 
-    Ok(max_year)
+    years.sort_unstable();
+    Ok(years)
 }
 
 /// Find the latest day number for a given year.
@@ -43,14 +47,20 @@ pub fn detect_latest_year() -> Result<Option<u32>> {
 /// This scans `crates/aoc{year}/src/bin` for files named `dNN.rs` and returns
 /// the maximum day value present, or `None` if no such files exist.
 pub fn detect_latest_day(year: u32) -> Result<Option<u8>> {
+    Ok(local_days(year)?.last().copied())
+}
+
+/// Every day with a `crates/aoc{year}/src/bin/dNN.rs` solution, in ascending
+/// order.
+pub fn local_days(year: u32) -> Result<Vec<u8>> {
     let pkg = format!("aoc{year}");
     let bin_dir = Path::new("crates").join(&pkg).join("src/bin");
 
     if !bin_dir.exists() {
-        return Ok(None);
+        return Ok(Vec::new());
     }
 
-    let max_day = fs::read_dir(bin_dir)?
+    let mut days: Vec<u8> = fs::read_dir(bin_dir)?
         .filter_map(|entry| entry.ok())
         .filter_map(|entry| {
             let name = entry.file_name().to_string_lossy().to_string();
@@ -58,7 +68,8 @@ pub fn detect_latest_day(year: u32) -> Result<Option<u8>> {
         })
         .filter_map(|name| name.strip_suffix(".rs").map(str::to_string))
         .filter_map(|num| num.parse::<u8>().ok())
-        .max();
+        .collect();
 
-    Ok(max_day)
+    days.sort_unstable();
+    Ok(days)
 }
@@ -29,3 +29,261 @@ pub fn extract_article_text(html: &str) -> Option<String> {
     let slice = &html[start..start + end_rel];
     Some(strip_tags(slice))
 }
+
+/// Inner HTML of every `<article>` block, in page order.
+///
+/// A puzzle page has one article per unlocked part.
+pub fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let body = &rest[start + open_end + 1..];
+        let Some(end) = body.find("</article>") else {
+            break;
+        };
+        articles.push(&body[..end]);
+        rest = &body[end..];
+    }
+
+    articles
+}
+
+/// Contents of every `<pre><code>` block, with tags removed and entities
+/// decoded, ready to be saved as an example input.
+pub fn extract_code_blocks(html: &str) -> Vec<String> {
+    let open = "<pre><code>";
+    html.match_indices(open)
+        .filter_map(|(i, _)| {
+            let rest = &html[i + open.len()..];
+            let end = rest.find("</code></pre>")?;
+            Some(decode_entities(&strip_tags(&rest[..end])))
+        })
+        .collect()
+}
+
+/// Render an article as plain text for the terminal.
+///
+/// Paragraphs and list items go on their own lines and `<pre>` blocks keep
+/// their line breaks; everything else is reduced to its text.
+pub fn render_article(html: &str) -> String {
+    let spaced = html
+        .replace("</h2>", "</h2>\n\n")
+        .replace("</p>", "</p>\n\n")
+        .replace("<li>", "<li>  - ")
+        .replace("</li>", "</li>\n")
+        .replace("</ul>", "</ul>\n\n")
+        .replace("</pre>", "</pre>\n");
+    let text = decode_entities(&strip_tags(&spaced));
+
+    let mut out = String::new();
+    let mut blank = false;
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() {
+            if !blank && !out.is_empty() {
+                out.push('\n');
+            }
+            blank = true;
+        } else {
+            out.push_str(line);
+            out.push('\n');
+            blank = false;
+        }
+    }
+    out.trim_end().to_string()
+}
+
+/// Decode the handful of HTML entities that appear on puzzle pages.
+pub fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Extract the logged-in user name from the page header.
+///
+/// Authenticated pages contain `<div class="user">NAME <span ...>`; pages
+/// served to a logged-out visitor have no such block and yield `None`.
+pub fn extract_user_name(html: &str) -> Option<String> {
+    let marker = r#"<div class="user">"#;
+    let start = html.find(marker)? + marker.len();
+    let end_rel = html[start..].find('<').unwrap_or(html.len() - start);
+    let name = html[start..start + end_rel].trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Extract the answers the site already accepted, in part order.
+///
+/// Once a part is solved its puzzle page shows `Your puzzle answer was
+/// <code>X</code>.` below the description.
+pub fn extract_accepted_answers(html: &str) -> Vec<String> {
+    let marker = "Your puzzle answer was <code>";
+    html.match_indices(marker)
+        .filter_map(|(i, _)| {
+            let rest = &html[i + marker.len()..];
+            let end = rest.find("</code>")?;
+            Some(strip_tags(&rest[..end]).trim().to_string())
+        })
+        .collect()
+}
+
+/// Stars per unlocked day on a year's calendar page, in day order.
+///
+/// Each unlocked day is a link such as `<a aria-label="Day 3, two stars"
+/// href="/2024/day/3" class="calendar-day3 calendar-verycomplete">`. The star
+/// count comes from the `aria-label`, falling back to the
+/// `calendar-complete` (one star) and `calendar-verycomplete` (two stars)
+/// classes. Days that are not links yet are still locked and left out.
+pub fn extract_calendar_stars(html: &str) -> Vec<(u8, u8)> {
+    let mut days: Vec<(u8, u8)> = html
+        .match_indices("<a ")
+        .filter_map(|(i, _)| {
+            let tag = &html[i..];
+            let tag = &tag[..tag.find('>')?];
+
+            let class = attribute(tag, "class")?;
+            let day = class
+                .split_whitespace()
+                .find_map(|c| c.strip_prefix("calendar-day")?.parse::<u8>().ok())?;
+
+            let label = attribute(tag, "aria-label").unwrap_or_default();
+            let stars =
+                if label.ends_with("two stars") || class.contains("calendar-verycomplete") {
+                    2
+                } else if label.ends_with("one star") || class.contains("calendar-complete") {
+                    1
+                } else {
+                    0
+                };
+            Some((day, stars))
+        })
+        .collect();
+
+    days.sort_unstable();
+    days.dedup_by_key(|(day, _)| *day);
+    days
+}
+
+/// Stars per event on the `/events` page, in page order (newest first).
+///
+/// Each event is a `<div class="eventlist-event"><a href="/2024">[2024]</a>
+/// <span class="star-count">34*</span></div>`; events without stars have no
+/// star count.
+pub fn extract_event_stars(html: &str) -> Vec<(u32, u32)> {
+    html.split(r#"class="eventlist-event""#)
+        .skip(1)
+        .filter_map(|event| {
+            let event = &event[..event.find("</div>").unwrap_or(event.len())];
+            let href = event.find(r#"href="/"#)? + r#"href="/"#.len();
+            let year = event[href..].split('"').next()?.parse().ok()?;
+
+            let marker = r#"<span class="star-count">"#;
+            let stars = event
+                .find(marker)
+                .and_then(|i| {
+                    event[i + marker.len()..]
+                        .split('*')
+                        .next()?
+                        .trim()
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(0);
+            Some((year, stars))
+        })
+        .collect()
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let marker = format!("{name}=\"");
+    let start = tag.find(&marker)? + marker.len();
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<main><article class=\"day-desc\"><h2>--- Day 1: X ---</h2><p>Sum \
+                        it.</p><pre><code>1\n2\n</code></pre></article><p>Your puzzle answer \
+                        was <code>3</code>.</p><article class=\"day-desc\"><h2 \
+                        id=\"part2\">--- Part Two ---</h2><p>Now <em>multiply</em> &amp; \
+                        compare:</p><ul><li>a &lt; \
+                        b</li></ul><pre><code>4\n<em>5</em>\n</code></pre></article></main>";
+
+    #[test]
+    fn articles_and_code_blocks() {
+        let articles = extract_articles(PAGE);
+        assert_eq!(articles.len(), 2);
+        assert!(articles[1].starts_with("<h2 id=\"part2\">"));
+
+        assert_eq!(extract_code_blocks(articles[0]), ["1\n2\n"]);
+        assert_eq!(extract_code_blocks(articles[1]), ["4\n5\n"]);
+    }
+
+    #[test]
+    fn renders_article_as_text() {
+        let text = render_article(extract_articles(PAGE)[1]);
+        assert_eq!(
+            text,
+            "--- Part Two ---\n\nNow multiply & compare:\n\n  - a < b\n\n4\n5"
+        );
+    }
+
+    #[test]
+    fn accepted_answers_in_part_order() {
+        let html = "<article>...</article><p>Your puzzle answer was \
+                    <code>1234</code>.</p><article>...</article><p>Your puzzle answer was \
+                    <code>ABC-9</code>.</p>";
+        assert_eq!(extract_accepted_answers(html), ["1234", "ABC-9"]);
+        assert!(extract_accepted_answers("<article>Day 1</article>").is_empty());
+    }
+
+    #[test]
+    fn calendar_stars_from_labels_and_classes() {
+        let html = r#"<pre class="calendar">
+<a aria-label="Day 1, two stars" href="/2024/day/1" class="calendar-day1 calendar-verycomplete">..</a>
+<a aria-label="Day 2, one star" href="/2024/day/2" class="calendar-day2 calendar-complete">..</a>
+<a href="/2024/day/3" class="calendar-day3 calendar-verycomplete">..</a>
+<a aria-label="Day 4" href="/2024/day/4" class="calendar-day4">..</a>
+<span class="calendar-day5">..</span>
+</pre>"#;
+        assert_eq!(extract_calendar_stars(html), [
+            (1, 2),
+            (2, 1),
+            (3, 2),
+            (4, 0)
+        ]);
+        assert!(extract_calendar_stars("<a href=\"/2024/events\">[Events]</a>").is_empty());
+    }
+
+    #[test]
+    fn event_stars_with_and_without_count() {
+        let html = r#"<main><div class="eventlist-event"><a href="/2025">[2025]</a> <span class="star-count">7*</span></div>
+<div class="eventlist-event"><a href="/2024">[2024]</a> <span class="star-count">50*</span></div>
+<div class="eventlist-event"><a href="/2023">[2023]</a></div></main>"#;
+        assert_eq!(extract_event_stars(html), [
+            (2025, 7),
+            (2024, 50),
+            (2023, 0)
+        ]);
+    }
+
+    #[test]
+    fn user_name_from_header() {
+        let html = r#"<header><div class="user">Jane Doe <span class="star-count">42*</span></div></header>"#;
+        assert_eq!(extract_user_name(html).as_deref(), Some("Jane Doe"));
+    }
+
+    #[test]
+    fn no_user_name_when_logged_out() {
+        let html = r#"<header><a href="/auth/login">[Log In]</a></header>"#;
+        assert_eq!(extract_user_name(html), None);
+    }
+}
@@ -0,0 +1,205 @@
+//! Salted, deliberately slow hashes of accepted answers.
+//!
+//! With `hash_answers = true` in the config, every accepted answer is also
+//! stored as a PBKDF2-HMAC-SHA256 hash in `answer-hashes/{year}.json` (scoped
+//! by profile like `answers/`). The salt is random per file, and each hash
+//! also covers the year, day and part, so equal answers on different days do
+//! not produce equal hashes. The number of rounds comes from
+//! `hash_iterations` and is stored in the file.
+//!
+//! The salt sits next to the hashes, and most answers are small integers, so
+//! the hashes only make guessing slow: anyone with the file can still recover
+//! an answer by hashing candidates until one matches.
+//!
+//! ```json
+//! {
+//!   "salt": "9f2c…",
+//!   "iterations": 600000,
+//!   "hashes": { "01/1": "5d41…", "01/2": "7b52…" }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::prelude::*;
+use crate::utils::config::Config;
+use crate::utils::ledger::Ledger;
+use crate::utils::profile::Profile;
+
+/// PBKDF2 rounds for new hash files when `hash_iterations` is not configured.
+///
+/// The count is stored in each file, so changing it later does not break
+/// existing hashes.
+pub const DEFAULT_ITERATIONS: u32 = 600_000;
+
+/// Hashes of the accepted answers for one year.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HashStore {
+    #[serde(skip)]
+    path:       PathBuf,
+    #[serde(skip)]
+    year:       u32,
+    salt:       String,
+    iterations: u32,
+    hashes:     BTreeMap<String, String>,
+}
+
+impl HashStore {
+    /// Load the store for `year` of the active profile.
+    pub fn load(year: u32) -> Result<Self> {
+        let iterations = Config::load()?
+            .hash_iterations
+            .unwrap_or(DEFAULT_ITERATIONS);
+        Self::load_from(
+            &Profile::active().answer_hashes_file(year),
+            year,
+            iterations,
+        )
+    }
+
+    /// Load the store kept in `path`, or an empty one hashing with
+    /// `iterations` rounds if it does not exist.
+    pub fn load_from(path: &Path, year: u32, iterations: u32) -> Result<Self> {
+        let mut store: Self = if path.exists() {
+            let text = fs::read_to_string(path)?;
+            serde_json::from_str(&text)
+                .map_err(|e| anyhow!("Invalid answer hashes {}: {e}", path.display()))?
+        } else {
+            Self {
+                iterations: iterations.max(1),
+                ..Self::default()
+            }
+        };
+        store.path = path.to_path_buf();
+        store.year = year;
+        Ok(store)
+    }
+
+    /// Record `answer` as accepted for `day`/`part` and write the file.
+    pub fn insert(&mut self, day: u8, part: u8, answer: &str) -> Result<()> {
+        if self.salt.is_empty() {
+            self.salt = random_salt()?;
+        }
+        let hash = self.hash(day, part, answer);
+        if self.hashes.insert(key(day, part), hash.clone()) == Some(hash) {
+            return Ok(());
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Whether `answer` matches the accepted answer for `day`/`part`, or
+    /// `None` if no hash is stored for it.
+    pub fn matches(&self, day: u8, part: u8, answer: &str) -> Option<bool> {
+        let stored = self.hashes.get(&key(day, part))?;
+        Some(*stored == self.hash(day, part, answer))
+    }
+
+    fn hash(&self, day: u8, part: u8, answer: &str) -> String {
+        let salt = format!("{}:{}:{day}:{part}", self.salt, self.year);
+        let mut digest = [0u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(
+            answer.trim().as_bytes(),
+            salt.as_bytes(),
+            self.iterations,
+            &mut digest,
+        );
+        hex(&digest)
+    }
+}
+
+/// Whether accepted answers are also kept as hashes.
+pub fn enabled() -> Result<bool> { Ok(Config::load()?.hash_answers) }
+
+/// Hash `answer` as accepted for `year`/`day`/`part` if hashing is enabled.
+pub fn record_accepted(year: u32, day: u8, part: u8, answer: &str) -> Result<()> {
+    if enabled()? {
+        HashStore::load(year)?.insert(day, part, answer)?;
+    }
+    Ok(())
+}
+
+/// Compare a fresh `output` with the accepted answer for `day`/`part`.
+///
+/// With hashing enabled the output is hashed and checked against the hash
+/// store; otherwise it is compared with the plain answer in `ledger`. `None`
+/// means no accepted answer is known.
+pub fn check_accepted(
+    year: u32,
+    ledger: &Ledger,
+    day: u8,
+    part: u8,
+    output: &str,
+) -> Result<Option<bool>> {
+    if enabled()? {
+        return Ok(HashStore::load(year)?.matches(day, part, output));
+    }
+    Ok(ledger
+        .accepted(day, part)
+        .map(|known| known == output.trim()))
+}
+
+fn key(day: u8, part: u8) -> String { format!("{day:02}/{part}") }
+
+fn random_salt() -> Result<String> {
+    let mut salt = [0u8; 16];
+    getrandom::fill(&mut salt).map_err(|e| anyhow!("Could not generate a salt: {e}"))?;
+    Ok(hex(&salt))
+}
+
+fn hex(bytes: &[u8]) -> String { bytes.iter().map(|b| format!("{b:02x}")).collect() }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_only_the_same_answer_for_the_same_part() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("2024.json");
+
+        let mut store = HashStore::load_from(&path, 2024, 1000).unwrap();
+        store.insert(1, 1, "1234").unwrap();
+
+        let store = HashStore::load_from(&path, 2024, 1).unwrap();
+        assert_eq!(store.matches(1, 1, "1234"), Some(true));
+        assert_eq!(store.matches(1, 1, " 1234\n"), Some(true));
+        assert_eq!(store.matches(1, 1, "1235"), Some(false));
+        assert_eq!(store.matches(1, 2, "1234"), None);
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("1234"), "{text}");
+        assert!(text.contains("\"01/1\""), "{text}");
+        assert!(text.contains("\"iterations\": 1000"), "{text}");
+    }
+
+    #[test]
+    fn salt_and_position_change_the_hash() {
+        let a = HashStore {
+            salt: "aa".to_string(),
+            year: 2024,
+            iterations: 1,
+            ..HashStore::default()
+        };
+        let b = HashStore {
+            salt: "bb".to_string(),
+            ..a.clone()
+        };
+        assert_ne!(a.hash(1, 1, "42"), b.hash(1, 1, "42"));
+        assert_ne!(a.hash(1, 1, "42"), a.hash(1, 2, "42"));
+        assert_ne!(a.hash(1, 1, "42"), a.hash(2, 1, "42"));
+        let slower = HashStore {
+            iterations: 2,
+            ..a.clone()
+        };
+        assert_ne!(a.hash(1, 1, "42"), slower.hash(1, 1, "42"));
+        assert_eq!(a.hash(1, 1, "42").len(), 64);
+    }
+}
@@ -1,8 +1,284 @@
 //! Minimal HTTP helpers for talking to the Advent of Code website.
+//!
+//! Read-only requests go through [`fetch`], which serves repeat requests from
+//! the on-disk [`Cache`] and refuses to touch the network when `--offline` is
+//! active. Responses are checked with [`response::classify`], so failures
+//! surface as typed [`AocError`](response::AocError)s with a hint attached.
 
-use reqwest::blocking::Client;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use std::{fmt, thread};
+
+use reqwest::blocking::{Client, RequestBuilder};
 
 use crate::prelude::*;
+use crate::utils::cache::Cache;
+use crate::utils::profile::Profile;
+use crate::utils::response::{self, Expect};
+use crate::utils::{config, throttle};
+
+/// Set from the global `--offline` flag before any command runs.
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// How many times a transient failure is retried before giving up.
+const MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry; doubled for every following one.
+const BACKOFF_BASE: Duration = Duration::from_millis(500);
+
+/// How long a cached puzzle page is served before it is fetched again.
+const PAGE_MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// How long a cached private leaderboard is served; the site asks clients not
+/// to request one more often than this.
+pub const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Something on the Advent of Code site that can be fetched and cached.
+///
+/// Everything here differs per user, so it is always fetched with the session
+/// cookie and cached in the profile's state directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    /// The personal puzzle input for a day.
+    Input { year: u32, day: u8 },
+    /// The puzzle description page for a day, as the logged-in user sees it,
+    /// including part two and accepted answers once unlocked.
+    Page { year: u32, day: u8 },
+    /// The year's calendar page, showing the user's stars for every
+    /// unlocked day.
+    Calendar { year: u32 },
+    /// The list of every event, with the user's star count for each.
+    Events,
+    /// A private leaderboard as JSON.
+    Leaderboard { year: u32, id: u64 },
+}
+
+impl Resource {
+    /// Path of this resource on the AoC site, e.g. `/2025/day/5/input`.
+    pub fn url_path(&self) -> String {
+        match *self {
+            Self::Input { year, day } => format!("/{year}/day/{day}/input"),
+            Self::Page { year, day } => format!("/{year}/day/{day}"),
+            Self::Calendar { year } => format!("/{year}"),
+            Self::Events => "/events".to_string(),
+            Self::Leaderboard { year, id } => {
+                format!("/{year}/leaderboard/private/view/{id}.json")
+            }
+        }
+    }
+
+    /// Location of this resource inside the cache directory.
+    pub fn cache_key(&self) -> PathBuf {
+        match *self {
+            Self::Input { year, day } => day_dir(year, day).join("input.txt"),
+            Self::Page { year, day } => day_dir(year, day).join("page.html"),
+            Self::Calendar { year } => Path::new(&year.to_string()).join("calendar.html"),
+            Self::Events => PathBuf::from("events.html"),
+            Self::Leaderboard { year, id } => {
+                Path::new(&year.to_string()).join(format!("leaderboard-{id}.json"))
+            }
+        }
+    }
+
+    /// How long a cached copy stays valid. Inputs never change.
+    pub const fn max_age(&self) -> Option<Duration> {
+        match self {
+            Self::Input { .. } => None,
+            Self::Page { .. } | Self::Calendar { .. } | Self::Events => Some(PAGE_MAX_AGE),
+            Self::Leaderboard { .. } => Some(LEADERBOARD_MAX_AGE),
+        }
+    }
+
+    /// What kind of body the site returns for this resource.
+    pub const fn expect(&self) -> Expect {
+        match self {
+            Self::Input { .. } | Self::Leaderboard { .. } => Expect::Text,
+            Self::Page { .. } | Self::Calendar { .. } | Self::Events => Expect::Html,
+        }
+    }
+}
+
+fn day_dir(year: u32, day: u8) -> PathBuf {
+    Path::new(&year.to_string()).join(format!("{day:02}"))
+}
+
+/// Enable or disable offline mode for the rest of the process.
+pub fn set_offline(offline: bool) { OFFLINE.store(offline, Ordering::Relaxed); }
+
+/// Whether the global `--offline` flag is active.
+pub fn is_offline() -> bool { OFFLINE.load(Ordering::Relaxed) }
+
+/// Fail with a clear error if offline mode forbids network access.
+///
+/// `action` describes what was about to happen, e.g. "submit an answer".
+pub fn ensure_online(action: &str) -> Result<()> {
+    if is_offline() {
+        return Err(anyhow!(
+            "Cannot {action} while --offline is set; rerun without --offline"
+        ));
+    }
+    Ok(())
+}
+
+/// Fetch a resource, serving it from the cache when a fresh copy exists.
+///
+/// Requests that do reach the network are spaced out by [`throttle::wait`].
+///
+/// In offline mode any cached copy is returned regardless of its age, and a
+/// missing entry is reported as an error instead of sending a request.
+pub fn fetch(resource: &Resource) -> Result<String> {
+    let cache = Cache::open(&config::base_url()?);
+    let key = resource.cache_key();
+    let cached = cache.get(&key)?;
+
+    if let Some(entry) = &cached
+        && (is_offline() || entry.is_fresh(resource.max_age()))
+    {
+        return Ok(entry.body.clone());
+    }
+
+    let body = get(&resource.url_path(), resource.expect())?;
+    cache.put(&key, &body)?;
+    Ok(body)
+}
+
+/// Send a GET request for `path`, bypassing the cache.
+///
+/// The request carries the session cookie, is throttled and is refused in
+/// offline mode. Failures are reported as [`AocError`]s; see [`send`] for
+/// retries.
+pub fn get(path: &str, expect: Expect) -> Result<String> {
+    ensure_online(&format!("fetch {path}"))?;
+
+    let url = url(path)?;
+    let cookie = format!("session={}", read_session()?);
+
+    send(path, expect, true, |client| {
+        client.get(&url).header(reqwest::header::COOKIE, &cookie)
+    })
+}
+
+/// Send an authenticated form POST to `path` and return the response body.
+///
+/// The request is throttled but never retried: a POST that failed with a
+/// server error or timeout may still have been processed, and sending it again
+/// could submit the same answer twice. Use [`is_transient`] to tell such
+/// failures apart.
+pub fn post_form(path: &str, form: &[(&str, String)]) -> Result<String> {
+    ensure_online(&format!("post to {path}"))?;
+
+    let url = url(path)?;
+    let cookie = format!("session={}", read_session()?);
+    let body = serde_urlencoded::to_string(form)?;
+
+    send(path, Expect::Html, false, |client| {
+        client
+            .post(&url)
+            .header(reqwest::header::COOKIE, &cookie)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .body(body.clone())
+    })
+}
+
+/// POST a JSON `body` to `url`, which is not on the AoC site.
+///
+/// Used for notification webhooks: the session cookie is never attached, and
+/// the request is neither throttled nor retried.
+pub fn post_json(url: &str, body: &str) -> Result<()> {
+    let resp = http_client()?
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .send()?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(anyhow!(
+            "HTTP {}: {url} rejected the request",
+            status.as_u16()
+        ));
+    }
+    Ok(())
+}
+
+/// Send a request built by `build`, retrying transient failures if `retry` is
+/// set.
+///
+/// Server errors and connection problems are retried up to [`MAX_RETRIES`]
+/// times with exponential backoff; every attempt goes through the throttle.
+/// Any other failure, or any failure at all without `retry`, is returned
+/// immediately.
+fn send(
+    path: &str,
+    expect: Expect,
+    retry: bool,
+    build: impl Fn(&Client) -> RequestBuilder,
+) -> Result<String> {
+    let client = http_client()?;
+
+    for attempt in 0.. {
+        throttle::wait()?;
+
+        let err: anyhow::Error = match build(&client).send() {
+            Ok(resp) => {
+                let status = resp.status().as_u16();
+                let body = resp.text()?;
+                match response::classify(status, &body, expect) {
+                    Ok(()) => return Ok(body),
+                    Err(e) => e.into(),
+                }
+            }
+            Err(e) => e.into(),
+        };
+
+        if !retry || !is_transient(&err) {
+            return Err(err);
+        }
+        if attempt >= MAX_RETRIES {
+            return Err(err.context(format!(
+                "Giving up on {path} after {} attempts",
+                attempt + 1
+            )));
+        }
+
+        let delay = backoff(attempt);
+        let reason = err.to_string();
+        eprintln!(
+            "Request to {path} failed ({}); retrying in {:.1}s",
+            reason.lines().next().unwrap_or_default(),
+            delay.as_secs_f64()
+        );
+        thread::sleep(delay);
+    }
+
+    unreachable!("the retry loop always returns")
+}
+
+/// Whether `err` is a server error or connection problem that may go away
+/// when the request is sent again.
+pub fn is_transient(err: &anyhow::Error) -> bool {
+    if let Some(e) = err.downcast_ref::<response::AocError>() {
+        return e.is_transient();
+    }
+    err.downcast_ref::<reqwest::Error>()
+        .is_some_and(|e| e.is_connect() || e.is_timeout())
+}
+
+/// Delay before retry number `attempt` (starting at zero).
+fn backoff(attempt: u32) -> Duration { BACKOFF_BASE * 2u32.pow(attempt) }
+
+/// Forget any cached copy of `resource`, e.g. after a submission changed it.
+pub fn invalidate(resource: &Resource) -> Result<()> {
+    Cache::open(&config::base_url()?).remove(resource.cache_key())
+}
+
+/// Build an absolute URL for `path` on the configured AoC site.
+///
+/// See [`config::base_url`] for how the site is chosen.
+pub fn url(path: &str) -> Result<String> { Ok(format!("{}{path}", config::base_url()?)) }
 
 /// Create a blocking HTTP client with a custom user agent.
 ///
@@ -14,39 +290,173 @@ pub fn http_client() -> Result<Client> {
     Ok(client)
 }
 
-/// Read the Advent of Code session cookie from the environment or from a file.
-///
-/// This first tries the `SESSION` environment variable, then falls back to
-/// several common file locations such as `.session` and `.config/aoc/session`.
-pub fn read_session() -> Result<String> {
-    // Environment variable wins.
-    if let Ok(value) = std::env::var("SESSION") {
-        let trimmed = value.trim();
-        if !trimmed.is_empty() {
-            return Ok(trimmed.to_string());
+/// Where a session token was found, or could have been found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionSource {
+    /// An environment variable with the given name.
+    Env(String),
+    /// A `KEY=value` entry in a dotenv file.
+    DotEnv { path: PathBuf, var: String },
+    /// A file on disk holding nothing but the token.
+    File(PathBuf),
+}
+
+impl SessionSource {
+    /// Read the token from this source, `None` if it is absent or empty.
+    fn read(&self) -> Result<Option<String>> {
+        let value = match self {
+            Self::Env(var) => std::env::var(var).ok(),
+            Self::DotEnv { path, var } if path.exists() => {
+                dotenv_value(&fs::read_to_string(path)?, var)
+            }
+            Self::File(path) if path.exists() => Some(fs::read_to_string(path)?),
+            Self::DotEnv { .. } | Self::File(_) => None,
+        };
+
+        Ok(value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty()))
+    }
+}
+
+impl fmt::Display for SessionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env(name) => write!(f, "environment variable {name}"),
+            Self::DotEnv { path, var } => write!(f, "{var} in {}", path.display()),
+            Self::File(path) => write!(f, "file {}", path.display()),
         }
     }
+}
 
-    // Try a few common file locations.
-    let candidates = [
-        ".session",
-        ".config/aoc/session",
-        "config/.session",
-        "aoc.session",
-    ];
+/// A session token together with where it came from.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub token:  String,
+    pub source: SessionSource,
+}
 
-    for path in candidates.iter().map(Path::new) {
-        if path.exists() {
-            let contents = fs::read_to_string(path)?;
-            let trimmed = contents.trim();
-            if !trimmed.is_empty() {
-                return Ok(trimmed.to_string());
-            }
+/// Read the Advent of Code session cookie.
+///
+/// See [`session_sources`] for the lookup order.
+pub fn read_session() -> Result<String> { Ok(find_session()?.token) }
+
+/// Like [`read_session`], but also report where the token was found.
+pub fn find_session() -> Result<Session> {
+    let sources = session_sources();
+
+    for source in &sources {
+        if let Some(token) = source.read()? {
+            return Ok(Session {
+                token,
+                source: source.clone(),
+            });
         }
     }
 
+    let checked: Vec<String> = sources.iter().map(|s| format!("  - {s}")).collect();
     Err(anyhow!(
-        "No AoC session token found. Set SESSION or create a `.session` file in the project \
-         root."
+        "No AoC session token found. Checked, in order:\n{}\nRun `aocctl auth set` or store \
+         the token in one of these locations.",
+        checked.join("\n")
     ))
 }
+
+/// Every place a session token is looked for, in priority order:
+///
+///   1. the `SESSION` environment variable,
+///   2. a `SESSION=...` line in `.env` in the workspace root,
+///   3. `$XDG_CONFIG_HOME/aoc/.session`, then `$HOME/.config/aoc/.session`,
+///   4. project-local files: `.session`, `config/.session`, `aoc.session`.
+///
+/// A named profile looks for `SESSION_{NAME}` and `.session.{name}` in the
+/// same places instead.
+pub fn session_sources() -> Vec<SessionSource> {
+    let profile = Profile::active();
+    let var = profile.session_var();
+    let file = profile.session_file();
+
+    let mut sources = vec![SessionSource::Env(var.clone()), SessionSource::DotEnv {
+        path: PathBuf::from(".env"),
+        var,
+    }];
+
+    let config_dirs = [
+        std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from),
+        std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")),
+    ];
+    let mut seen = Vec::new();
+    for dir in config_dirs.into_iter().flatten() {
+        let path = dir.join("aoc").join(&file);
+        if !seen.contains(&path) {
+            seen.push(path.clone());
+            sources.push(SessionSource::File(path));
+        }
+    }
+
+    match profile.name() {
+        Some(_) => sources.push(SessionSource::File(file)),
+        None => sources.extend(
+            [".session", "config/.session", "aoc.session"]
+                .map(|p| SessionSource::File(p.into())),
+        ),
+    }
+
+    sources
+}
+
+/// Look up `key` in the contents of a dotenv file.
+///
+/// Supports blank lines, `#` comments, an optional `export ` prefix and
+/// single or double quoted values.
+fn dotenv_value(text: &str, key: &str) -> Option<String> {
+    text.lines().rev().find_map(|line| {
+        let line = line.trim();
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (k, v) = line.split_once('=')?;
+        if k.trim() != key {
+            return None;
+        }
+
+        let v = v.trim();
+        let quoted = ['"', '\''].into_iter().find_map(|q| {
+            v.strip_prefix(q)
+                .and_then(|rest| rest.split_once(q))
+                .map(|(inner, _)| inner)
+        });
+        let value = quoted.unwrap_or_else(|| v.split(" #").next().unwrap_or(v));
+        Some(value.trim().to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dotenv_plain_quoted_and_exported() {
+        let text = "# comment\nOTHER=1\nSESSION=abc123\n";
+        assert_eq!(dotenv_value(text, "SESSION").as_deref(), Some("abc123"));
+
+        let text = "export SESSION=\"abc 123\"\n";
+        assert_eq!(dotenv_value(text, "SESSION").as_deref(), Some("abc 123"));
+
+        let text = "SESSION='abc' # trailing\n";
+        assert_eq!(dotenv_value(text, "SESSION").as_deref(), Some("abc"));
+
+        let text = "SESSION=abc # trailing\n";
+        assert_eq!(dotenv_value(text, "SESSION").as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn dotenv_missing_key_or_prefix_match() {
+        assert_eq!(dotenv_value("SESSION_WORK=abc\n", "SESSION"), None);
+        assert_eq!(dotenv_value("# SESSION=abc\n", "SESSION"), None);
+    }
+
+    #[test]
+    fn dotenv_last_assignment_wins() {
+        let text = "SESSION=old\nSESSION=new\n";
+        assert_eq!(dotenv_value(text, "SESSION").as_deref(), Some("new"));
+    }
+}
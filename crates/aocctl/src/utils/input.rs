@@ -1,6 +1,7 @@
 //! Helpers for resolving input file paths.
 
 use crate::prelude::*;
+use crate::utils::profile::Profile;
 
 /// Resolve an input path for the given year and day.
 ///
@@ -8,15 +9,16 @@ use crate::prelude::*;
 ///   * `inputs/{year}/d{day:02}.txt`
 ///   * `inputs/{year}/input.txt`
 ///
+/// With a named profile active, `inputs/{profile}/{year}` is used instead.
+///
 /// If `input` is anything else, it is treated as a filesystem path and must
 /// exist.
 pub fn resolve_input_path(year: u32, day: u8, input: impl AsRef<str>) -> Result<PathBuf> {
     let input = input.as_ref();
     if input == "puzzle" {
-        let specific = Path::new("inputs")
-            .join(year.to_string())
-            .join(format!("d{day:02}.txt"));
-        let generic = Path::new("inputs").join(year.to_string()).join("input.txt");
+        let dir = Profile::active().inputs_dir(year);
+        let specific = dir.join(format!("d{day:02}.txt"));
+        let generic = dir.join("input.txt");
 
         if specific.exists() {
             return Ok(specific);
@@ -0,0 +1,447 @@
+//! Private leaderboards as served by
+//! `/{year}/leaderboard/private/view/{id}.json`.
+//!
+//! ```json
+//! {
+//!   "event": "2025",
+//!   "owner_id": 1,
+//!   "members": {
+//!     "1": {
+//!       "id": 1, "name": "alice", "stars": 3, "local_score": 8,
+//!       "last_star_ts": 1764652600,
+//!       "completion_day_level": {
+//!         "1": { "1": { "get_star_ts": 1764565500, "star_index": 10 } }
+//!       }
+//!     }
+//!   }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::utils::profile::Profile;
+use crate::utils::{calendar, http};
+
+/// A private leaderboard snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    /// The event year, as a string like `"2025"`.
+    pub event:    String,
+    pub owner_id: u64,
+    /// Members keyed by their id.
+    pub members:  BTreeMap<String, Member>,
+}
+
+/// One member of a private leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Member {
+    pub id:                   u64,
+    /// `None` for anonymous users.
+    pub name:                 Option<String>,
+    pub stars:                u32,
+    pub local_score:          u32,
+    /// Unix seconds of the member's latest star, `0` if they have none.
+    #[serde(default)]
+    pub last_star_ts:         u64,
+    /// Stars earned, by day and then by part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+/// When a member earned one star.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Star {
+    /// Unix seconds at which the part was solved.
+    pub get_star_ts: u64,
+    #[serde(default)]
+    pub star_index:  u64,
+}
+
+impl Leaderboard {
+    /// Fetch leaderboard `id` for `year` with the session cookie.
+    ///
+    /// The site asks for no more than one request every 15 minutes, so the
+    /// response is cached for that long.
+    pub fn fetch(year: u32, id: u64) -> Result<Self> {
+        let body = http::fetch(&http::Resource::Leaderboard { year, id })?;
+        Self::parse(&body)
+    }
+
+    /// Load a leaderboard saved as JSON at `path`.
+    pub fn load_file(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Could not read {}: {e}", path.display()))?;
+        Self::parse(&text).map_err(|e| anyhow!("{}: {e}", path.display()))
+    }
+
+    /// Parse the leaderboard JSON.
+    pub fn parse(text: &str) -> Result<Self> {
+        serde_json::from_str(text).map_err(|e| anyhow!("Invalid leaderboard JSON: {e}"))
+    }
+
+    /// The event year.
+    pub fn year(&self) -> Result<u32> {
+        self.event
+            .parse()
+            .map_err(|_| anyhow!("Invalid leaderboard event {:?}", self.event))
+    }
+
+    /// Where `leaderboard --watch` keeps the last snapshot of leaderboard
+    /// `id` for `year`.
+    pub fn snapshot_path(year: u32, id: u64) -> PathBuf {
+        Profile::active()
+            .state_dir()
+            .join("leaderboards")
+            .join(format!("{year}-{id}.json"))
+    }
+
+    /// Load the snapshot saved at `path`, if there is one.
+    pub fn load_snapshot(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::load_file(path).map(Some)
+    }
+
+    /// Save this leaderboard as the snapshot at `path`.
+    pub fn save_snapshot(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Local-score points earned by every star, keyed by member id, day and
+    /// part.
+    ///
+    /// With `n` members, the first member to solve a part gets `n` points, the
+    /// second `n - 1`, and so on.
+    pub fn star_points(&self) -> BTreeMap<(u64, u8, u8), u32> {
+        let mut solves: BTreeMap<(u8, u8), Vec<(u64, u64)>> = BTreeMap::new();
+        for member in self.members.values() {
+            for (&day, parts) in &member.completion_day_level {
+                for (&part, star) in parts {
+                    solves
+                        .entry((day, part))
+                        .or_default()
+                        .push((star.get_star_ts, member.id));
+                }
+            }
+        }
+
+        let n = self.members.len() as u32;
+        let mut points = BTreeMap::new();
+        for ((day, part), mut order) in solves {
+            order.sort_unstable();
+            for (rank, (_, id)) in order.into_iter().enumerate() {
+                points.insert((id, day, part), n.saturating_sub(rank as u32));
+            }
+        }
+        points
+    }
+
+    /// Per-member statistics, best recomputed score first.
+    ///
+    /// Scores are recomputed from the star times with [`Self::star_points`],
+    /// ignoring stars on the `excluded` days (for example a day whose puzzle
+    /// was broken). Every member gets one [`DayStats`] per day up to the last
+    /// day anyone has a star on.
+    pub fn stats(&self, excluded: &[u8]) -> Result<Vec<MemberStats<'_>>> {
+        let year = self.year()?;
+        let points = self.star_points();
+        let last_day = self
+            .members
+            .values()
+            .filter_map(|m| m.completion_day_level.keys().max())
+            .max()
+            .copied()
+            .unwrap_or(0);
+
+        let mut stats: Vec<MemberStats<'_>> = self
+            .members
+            .values()
+            .map(|member| {
+                let mut score = 0;
+                let days = (1..=last_day)
+                    .map(|day| {
+                        let earned = if excluded.contains(&day) {
+                            0
+                        } else {
+                            (1..=2)
+                                .filter_map(|part| points.get(&(member.id, day, part)))
+                                .sum()
+                        };
+                        score += earned;
+                        DayStats {
+                            day,
+                            part1: member.star_elapsed(year, day, 1),
+                            part2: member.star_elapsed(year, day, 2),
+                            points: earned,
+                            score,
+                            rank: 0,
+                        }
+                    })
+                    .collect();
+                MemberStats {
+                    member,
+                    score,
+                    days,
+                }
+            })
+            .collect();
+
+        // Competition ranking after each day: ties share a rank.
+        for i in 0..usize::from(last_day) {
+            let scores: Vec<u32> = stats.iter().map(|s| s.days[i].score).collect();
+            for s in &mut stats {
+                let own = s.days[i].score;
+                s.days[i].rank = 1 + scores.iter().filter(|&&other| other > own).count() as u32;
+            }
+        }
+
+        stats.sort_by_key(|s| {
+            (
+                std::cmp::Reverse(s.score),
+                s.member.last_star_ts,
+                s.member.id,
+            )
+        });
+        Ok(stats)
+    }
+
+    /// Stars in this leaderboard that `previous` did not have yet, oldest
+    /// first.
+    pub fn new_stars_since(&self, previous: &Self) -> Vec<StarEvent> {
+        let points = self.star_points();
+        let mut events = Vec::new();
+
+        for member in self.members.values() {
+            let before = previous.members.get(&member.id.to_string());
+            for (&day, parts) in &member.completion_day_level {
+                for (&part, star) in parts {
+                    if before.is_some_and(|m| m.has_star(day, part)) {
+                        continue;
+                    }
+                    events.push(StarEvent {
+                        member_id: member.id,
+                        member: member.display_name(),
+                        day,
+                        part,
+                        points: points.get(&(member.id, day, part)).copied().unwrap_or(0),
+                        time: star.get_star_ts,
+                    });
+                }
+            }
+        }
+
+        events.sort_by_key(|e| (e.time, e.member_id, e.day, e.part));
+        events
+    }
+
+    /// Members in leaderboard order: highest local score first, ties going to
+    /// whoever got their last star earlier.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id));
+        members
+    }
+}
+
+impl Member {
+    /// The member's name, or how the site shows anonymous users.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Whether the member solved `part` of `day`.
+    pub fn has_star(&self, day: u8, part: u8) -> bool {
+        self.completion_day_level
+            .get(&day)
+            .is_some_and(|parts| parts.contains_key(&part))
+    }
+
+    /// Time from the unlock of `year`/`day` until the member solved `part`.
+    pub fn star_elapsed(&self, year: u32, day: u8, part: u8) -> Option<Duration> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        let at = UNIX_EPOCH + Duration::from_secs(star.get_star_ts);
+        Some(
+            at.duration_since(calendar::unlock_time(year, day))
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Number of parts (0, 1 or 2) solved on `day`.
+    pub fn parts_on(&self, day: u8) -> usize {
+        self.completion_day_level.get(&day).map_or(0, BTreeMap::len)
+    }
+}
+
+/// Statistics for one member, see [`Leaderboard::stats`].
+#[derive(Debug, Clone)]
+pub struct MemberStats<'a> {
+    pub member: &'a Member,
+    /// Recomputed local score.
+    pub score:  u32,
+    pub days:   Vec<DayStats>,
+}
+
+/// One member's results for one day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayStats {
+    pub day:    u8,
+    /// Time from unlock to the first star.
+    pub part1:  Option<Duration>,
+    /// Time from unlock to the second star.
+    pub part2:  Option<Duration>,
+    /// Points earned on this day, `0` for an excluded day.
+    pub points: u32,
+    /// Recomputed score after this day.
+    pub score:  u32,
+    /// Rank after this day.
+    pub rank:   u32,
+}
+
+impl DayStats {
+    /// Time from the first star to the second.
+    pub fn delta(&self) -> Option<Duration> { Some(self.part2?.saturating_sub(self.part1?)) }
+}
+
+/// A star that appeared between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StarEvent {
+    pub member_id: u64,
+    pub member:    String,
+    pub day:       u8,
+    pub part:      u8,
+    /// Local-score points the star earned.
+    pub points:    u32,
+    /// Unix seconds at which the part was solved.
+    pub time:      u64,
+}
+
+impl fmt::Display for StarEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} got day {} part {} (+{} pts)",
+            self.member, self.day, self.part, self.points
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "event": "2025",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "alice", "stars": 2, "local_score": 4,
+                  "last_star_ts": 1764566100,
+                  "completion_day_level": {"1": {
+                      "1": {"get_star_ts": 1764565500, "star_index": 1},
+                      "2": {"get_star_ts": 1764566100, "star_index": 4}}}},
+            "2": {"id": 2, "name": "bob", "stars": 2, "local_score": 4,
+                  "last_star_ts": 1764565800,
+                  "completion_day_level": {"1": {
+                      "1": {"get_star_ts": 1764565600, "star_index": 2},
+                      "2": {"get_star_ts": 1764565800, "star_index": 3}}}},
+            "3": {"id": 3, "name": null, "stars": 0, "local_score": 0,
+                  "last_star_ts": 0, "completion_day_level": {}}
+        }
+    }"#;
+
+    #[test]
+    fn parses_members_and_stars() {
+        let board = Leaderboard::parse(JSON).unwrap();
+        assert_eq!(board.year().unwrap(), 2025);
+
+        let alice = &board.members["1"];
+        assert_eq!(alice.parts_on(1), 2);
+        assert_eq!(alice.parts_on(2), 0);
+        assert_eq!(alice.completion_day_level[&1][&2].get_star_ts, 1764566100);
+        assert_eq!(board.members["3"].display_name(), "(anonymous user #3)");
+    }
+
+    #[test]
+    fn ties_go_to_the_earlier_last_star() {
+        let board = Leaderboard::parse(JSON).unwrap();
+        let names: Vec<String> = board.ranked().iter().map(|m| m.display_name()).collect();
+        assert_eq!(names, ["bob", "alice", "(anonymous user #3)"]);
+    }
+
+    #[test]
+    fn points_follow_solve_order() {
+        let board = Leaderboard::parse(JSON).unwrap();
+        let points = board.star_points();
+        assert_eq!(points[&(1, 1, 1)], 3);
+        assert_eq!(points[&(2, 1, 1)], 2);
+        assert_eq!(points[&(2, 1, 2)], 3);
+        assert_eq!(points[&(1, 1, 2)], 2);
+        assert_eq!(points.len(), 4);
+    }
+
+    #[test]
+    fn new_stars_are_reported_in_time_order() {
+        let now = Leaderboard::parse(JSON).unwrap();
+        let mut before = now.clone();
+        before
+            .members
+            .get_mut("1")
+            .unwrap()
+            .completion_day_level
+            .clear();
+        before
+            .members
+            .get_mut("2")
+            .unwrap()
+            .completion_day_level
+            .get_mut(&1)
+            .unwrap()
+            .remove(&2);
+
+        let events: Vec<String> = now
+            .new_stars_since(&before)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(events, [
+            "alice got day 1 part 1 (+3 pts)",
+            "bob got day 1 part 2 (+3 pts)",
+            "alice got day 1 part 2 (+2 pts)",
+        ]);
+        assert!(now.new_stars_since(&now).is_empty());
+    }
+
+    #[test]
+    fn stats_recompute_scores_and_ranks() {
+        let board = Leaderboard::parse(JSON).unwrap();
+
+        let stats = board.stats(&[]).unwrap();
+        let alice = stats.iter().find(|s| s.member.id == 1).unwrap();
+        assert_eq!(alice.score, 5);
+        assert_eq!(alice.days.len(), 1);
+        let day = alice.days[0];
+        assert_eq!(day.part1, Some(Duration::from_secs(300)));
+        assert_eq!(day.delta(), Some(Duration::from_secs(600)));
+        assert_eq!(day.rank, 1);
+        assert_eq!(stats[2].days[0].rank, 3);
+
+        let stats = board.stats(&[1]).unwrap();
+        assert!(stats.iter().all(|s| s.score == 0 && s.days[0].rank == 1));
+    }
+
+    #[test]
+    fn rejects_other_json() {
+        assert!(Leaderboard::parse(r#"{"error": "nope"}"#).is_err());
+    }
+}
@@ -0,0 +1,293 @@
+//! Per-year record of every answer submitted, kept in
+//! `answers/{year}/ledger.json` (or `answers/{profile}/{year}` for a named
+//! profile).
+//!
+//! Earlier versions appended `part N: answer` lines to
+//! `answers/{year}/dNN.txt`. When a year has no ledger yet, those files are
+//! imported on load, without a time or verdict, and left in place.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::utils::profile::Profile;
+use crate::utils::verdict::SubmissionVerdict;
+
+/// One submitted (or dry-run) answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day:     u8,
+    pub part:    u8,
+    pub answer:  String,
+    /// Unix seconds when the answer was sent; `None` for imported entries.
+    #[serde(default)]
+    pub time:    Option<u64>,
+    /// The site's response; `None` for dry runs and imported entries.
+    #[serde(default)]
+    pub verdict: Option<SubmissionVerdict>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl Attempt {
+    /// A new attempt stamped with the current time.
+    pub fn now(day: u8, part: u8, answer: &str) -> Self {
+        Self {
+            day,
+            part,
+            answer: answer.to_string(),
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+            verdict: None,
+            dry_run: false,
+        }
+    }
+}
+
+/// All attempts for one year, oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(skip)]
+    dir:          PathBuf,
+    pub attempts: Vec<Attempt>,
+}
+
+impl Ledger {
+    /// Load the ledger for `year` of the active profile.
+    pub fn load(year: u32) -> Result<Self> {
+        Self::load_from(&Profile::active().answers_dir(year))
+    }
+
+    /// Load the ledger kept in `dir`, importing legacy `dNN.txt` logs if
+    /// there is no ledger file yet.
+    pub fn load_from(dir: &Path) -> Result<Self> {
+        let path = dir.join("ledger.json");
+        let mut ledger = if path.exists() {
+            let text = fs::read_to_string(&path)?;
+            serde_json::from_str(&text)
+                .map_err(|e| anyhow!("Invalid answer ledger {}: {e}", path.display()))?
+        } else {
+            Self {
+                attempts: import_legacy(dir)?,
+                ..Self::default()
+            }
+        };
+        ledger.dir = dir.to_path_buf();
+        Ok(ledger)
+    }
+
+    /// Location of the ledger file.
+    pub fn path(&self) -> PathBuf { self.dir.join("ledger.json") }
+
+    /// Append `attempt` and write the ledger back to disk.
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        self.attempts.push(attempt);
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(), serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Why `answer` for `day`/`part` is known to be wrong, if it is.
+    ///
+    /// An answer is refused if the site already rejected exactly this value,
+    /// or, for integers, if earlier too-high and too-low verdicts rule it out.
+    pub fn known_wrong(&self, day: u8, part: u8, answer: &str) -> Option<String> {
+        let answer = answer.trim();
+        let judged = self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part && !a.dry_run)
+            .filter_map(|a| Some((a, a.verdict.as_ref()?)));
+
+        // Lowest answer that was too high and highest that was too low.
+        let mut too_high = None::<i128>;
+        let mut too_low = None::<i128>;
+
+        for (attempt, verdict) in judged {
+            let rejected = matches!(
+                verdict,
+                SubmissionVerdict::Incorrect
+                    | SubmissionVerdict::TooHigh
+                    | SubmissionVerdict::TooLow
+            );
+            if rejected && attempt.answer == answer {
+                return Some(format!("{answer} was already rejected as {verdict}"));
+            }
+
+            let Ok(n) = attempt.answer.parse::<i128>() else {
+                continue;
+            };
+            match verdict {
+                SubmissionVerdict::TooHigh => too_high = Some(too_high.map_or(n, |h| h.min(n))),
+                SubmissionVerdict::TooLow => too_low = Some(too_low.map_or(n, |l| l.max(n))),
+                _ => {}
+            }
+        }
+
+        let n = answer.parse::<i128>().ok()?;
+        if let Some(high) = too_high.filter(|high| n >= *high) {
+            return Some(format!(
+                "{answer} is not below {high}, which was already too high"
+            ));
+        }
+        if let Some(low) = too_low.filter(|low| n <= *low) {
+            return Some(format!(
+                "{answer} is not above {low}, which was already too low"
+            ));
+        }
+        None
+    }
+
+    /// The answer the site accepted for `day`/`part`, if one is recorded.
+    pub fn accepted(&self, day: u8, part: u8) -> Option<&str> {
+        self.attempts
+            .iter()
+            .rev()
+            .find(|a| {
+                a.day == day && a.part == part && a.verdict == Some(SubmissionVerdict::Correct)
+            })
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Attempts for `day`, or all attempts if `day` is `None`.
+    pub fn for_day(&self, day: Option<u8>) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| day.is_none_or(|d| a.day == d))
+    }
+}
+
+/// Read every `dNN.txt` log in `dir` into attempts, ordered by day.
+fn import_legacy(dir: &Path) -> Result<Vec<Attempt>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut logs: Vec<(u8, PathBuf)> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?;
+            let day = stem.strip_prefix('d')?.parse().ok()?;
+            (path.extension()? == "txt").then_some((day, path))
+        })
+        .collect();
+    logs.sort();
+
+    let mut attempts = Vec::new();
+    for (day, path) in logs {
+        attempts.extend(parse_legacy(day, &fs::read_to_string(&path)?));
+    }
+    Ok(attempts)
+}
+
+/// Parse `part N: answer` lines from a legacy log.
+fn parse_legacy(day: u8, text: &str) -> impl Iterator<Item = Attempt> + '_ {
+    text.lines().filter_map(move |line| {
+        let (part, answer) = line.strip_prefix("part ")?.split_once(':')?;
+        Some(Attempt {
+            day,
+            part: part.trim().parse().ok()?,
+            answer: answer.trim().to_string(),
+            time: None,
+            verdict: None,
+            dry_run: false,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn judged(part: u8, answer: &str, verdict: SubmissionVerdict) -> Attempt {
+        Attempt {
+            verdict: Some(verdict),
+            ..Attempt::now(1, part, answer)
+        }
+    }
+
+    #[test]
+    fn refuses_rejected_and_out_of_bounds_answers() {
+        let ledger = Ledger {
+            attempts: vec![
+                judged(1, "abc", SubmissionVerdict::Incorrect),
+                judged(1, "100", SubmissionVerdict::TooHigh),
+                judged(1, "50", SubmissionVerdict::TooHigh),
+                judged(1, "10", SubmissionVerdict::TooLow),
+                judged(1, "13", SubmissionVerdict::RateLimited { wait: None }),
+            ],
+            ..Ledger::default()
+        };
+
+        let refused = |answer| ledger.known_wrong(1, 1, answer);
+        assert!(
+            refused("abc")
+                .unwrap()
+                .contains("already rejected as incorrect")
+        );
+        assert!(refused("50").unwrap().contains("already rejected"));
+        assert!(refused("60").unwrap().contains("not below 50"));
+        assert!(refused("10").unwrap().contains("already rejected"));
+        assert!(refused("-3").unwrap().contains("not above 10"));
+        assert_eq!(refused("13"), None);
+        assert_eq!(refused("49"), None);
+        assert_eq!(refused("xyz"), None);
+        assert_eq!(ledger.known_wrong(1, 2, "100"), None);
+    }
+
+    #[test]
+    fn imports_legacy_logs_once() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("d02.txt"), "part 1: 7\n").unwrap();
+        fs::write(dir.path().join("d01.txt"), "part 1: 15\npart 2: 120\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "part 1: ignored\n").unwrap();
+
+        let mut ledger = Ledger::load_from(dir.path()).unwrap();
+        let answers: Vec<(u8, u8, &str)> = ledger
+            .attempts
+            .iter()
+            .map(|a| (a.day, a.part, a.answer.as_str()))
+            .collect();
+        assert_eq!(answers, [(1, 1, "15"), (1, 2, "120"), (2, 1, "7")]);
+        assert!(ledger.attempts.iter().all(|a| a.time.is_none()));
+
+        ledger.record(Attempt::now(2, 2, "9")).unwrap();
+        fs::write(dir.path().join("d03.txt"), "part 1: 1\n").unwrap();
+
+        let ledger = Ledger::load_from(dir.path()).unwrap();
+        assert_eq!(ledger.attempts.len(), 4);
+        assert!(dir.path().join("d01.txt").exists());
+    }
+
+    #[test]
+    fn round_trips_verdicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ledger = Ledger::load_from(dir.path()).unwrap();
+
+        let verdicts = [
+            SubmissionVerdict::TooLow,
+            SubmissionVerdict::RateLimited {
+                wait: Some(Duration::from_secs(43)),
+            },
+            SubmissionVerdict::Unknown("odd".to_string()),
+        ];
+        for verdict in &verdicts {
+            let mut attempt = Attempt::now(1, 1, "5");
+            attempt.verdict = Some(verdict.clone());
+            ledger.record(attempt).unwrap();
+        }
+
+        let loaded = Ledger::load_from(dir.path()).unwrap();
+        let got: Vec<_> = loaded
+            .for_day(Some(1))
+            .filter_map(|a| a.verdict.clone())
+            .collect();
+        assert_eq!(got, verdicts);
+        assert_eq!(loaded.for_day(Some(2)).count(), 0);
+    }
+}
@@ -0,0 +1,155 @@
+//! Named account profiles.
+//!
+//! Without `--profile` everything uses the historical single-account layout
+//! (`inputs/{year}`, `answers/{year}`, `.session`). A named profile gets its
+//! own session token, inputs, answers and cache:
+//!
+//! | What         | Default profile             | Profile `work`                   |
+//! | ------------ | --------------------------- | -------------------------------- |
+//! | inputs       | `inputs/{year}`             | `inputs/work/{year}`             |
+//! | answers      | `answers/{year}`            | `answers/work/{year}`            |
+//! | hashes       | `answer-hashes/{year}.json` | `answer-hashes/work/{year}.json` |
+//! | session var  | `SESSION`                   | `SESSION_WORK`                   |
+//! | session file | `.session`                  | `.session.work`                  |
+//! | state/cache  | `.aocctl`                   | `.aocctl/profiles/work`          |
+
+use std::sync::OnceLock;
+
+use crate::prelude::*;
+
+static ACTIVE: OnceLock<Profile> = OnceLock::new();
+
+/// An account profile, either the default one or a named one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    name: Option<String>,
+}
+
+impl Profile {
+    /// A named profile, validating that the name is safe to use in paths.
+    pub fn named(name: &str) -> Result<Self> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            && !name.chars().all(|c| c.is_ascii_digit());
+
+        if !valid {
+            return Err(anyhow!(
+                "Invalid profile name {name:?}: use letters, digits, '-' or '_', and not only \
+                 digits"
+            ));
+        }
+
+        Ok(Self {
+            name: Some(name.to_string()),
+        })
+    }
+
+    /// Select the profile used for the rest of the process.
+    ///
+    /// Called once from the CLI entry point with the `--profile` value.
+    pub fn activate(name: Option<&str>) -> Result<()> {
+        let profile = match name {
+            Some(name) => Self::named(name)?,
+            None => Self::default(),
+        };
+        ACTIVE
+            .set(profile)
+            .map_err(|_| anyhow!("A profile has already been activated"))
+    }
+
+    /// The active profile, or the default profile if none was activated.
+    pub fn active() -> &'static Self { ACTIVE.get_or_init(Self::default) }
+
+    /// Profile name, `None` for the default profile.
+    pub fn name(&self) -> Option<&str> { self.name.as_deref() }
+
+    /// Directory holding puzzle inputs for `year`.
+    pub fn inputs_dir(&self, year: u32) -> PathBuf {
+        self.scoped("inputs").join(year.to_string())
+    }
+
+    /// Directory holding submission records for `year`.
+    pub fn answers_dir(&self, year: u32) -> PathBuf {
+        self.scoped("answers").join(year.to_string())
+    }
+
+    /// File holding salted hashes of the accepted answers for `year`.
+    pub fn answer_hashes_file(&self, year: u32) -> PathBuf {
+        self.scoped("answer-hashes").join(format!("{year}.json"))
+    }
+
+    /// Directory for per-profile tool state such as the cache.
+    pub fn state_dir(&self) -> PathBuf {
+        let root = Path::new(".aocctl");
+        match &self.name {
+            Some(name) => root.join("profiles").join(name),
+            None => root.to_path_buf(),
+        }
+    }
+
+    /// Environment variable holding this profile's session token.
+    pub fn session_var(&self) -> String {
+        match &self.name {
+            Some(name) => format!("SESSION_{}", name.to_ascii_uppercase().replace('-', "_")),
+            None => "SESSION".to_string(),
+        }
+    }
+
+    /// Project-local file holding this profile's session token.
+    pub fn session_file(&self) -> PathBuf {
+        match &self.name {
+            Some(name) => PathBuf::from(format!(".session.{name}")),
+            None => PathBuf::from(".session"),
+        }
+    }
+
+    fn scoped(&self, dir: &str) -> PathBuf {
+        match &self.name {
+            Some(name) => Path::new(dir).join(name),
+            None => PathBuf::from(dir),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_profile_keeps_single_account_layout() {
+        let p = Profile::default();
+        assert_eq!(p.inputs_dir(2025), Path::new("inputs/2025"));
+        assert_eq!(p.answers_dir(2025), Path::new("answers/2025"));
+        assert_eq!(
+            p.answer_hashes_file(2025),
+            Path::new("answer-hashes/2025.json")
+        );
+        assert_eq!(p.session_var(), "SESSION");
+        assert_eq!(p.session_file(), Path::new(".session"));
+        assert_eq!(p.state_dir(), Path::new(".aocctl"));
+    }
+
+    #[test]
+    fn named_profile_is_scoped() {
+        let p = Profile::named("work-2").unwrap();
+        assert_eq!(p.inputs_dir(2025), Path::new("inputs/work-2/2025"));
+        assert_eq!(p.answers_dir(2025), Path::new("answers/work-2/2025"));
+        assert_eq!(
+            p.answer_hashes_file(2025),
+            Path::new("answer-hashes/work-2/2025.json")
+        );
+        assert_eq!(p.session_var(), "SESSION_WORK_2");
+        assert_eq!(p.session_file(), Path::new(".session.work-2"));
+        assert_eq!(p.state_dir(), Path::new(".aocctl/profiles/work-2"));
+    }
+
+    #[test]
+    fn rejects_unsafe_or_numeric_names() {
+        assert!(Profile::named("").is_err());
+        assert!(Profile::named("../etc").is_err());
+        assert!(Profile::named("a b").is_err());
+        assert!(Profile::named("2025").is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! Classification of Advent of Code HTTP responses into typed errors.
+//!
+//! The site signals most problems through a handful of status codes and
+//! bodies. [`classify`] turns those into an [`AocError`] that carries a hint
+//! for what to do next, and tells the HTTP layer which failures are worth
+//! retrying.
+
+use std::fmt;
+
+/// Body the site sends when the session cookie is missing or not accepted.
+const SESSION_REJECTED: &str = "Puzzle inputs differ by user";
+
+/// What kind of body a request is expected to return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expect {
+    /// Plain text such as a puzzle input or leaderboard JSON. An HTML page is
+    /// an error.
+    Text,
+    /// An HTML page.
+    Html,
+}
+
+/// A failed request to the Advent of Code site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    /// The session cookie is missing, expired or invalid.
+    InvalidSession { status: u16 },
+    /// The puzzle is not unlocked yet, or does not exist.
+    NotUnlocked,
+    /// A login page was served where puzzle input was expected.
+    LoginPage,
+    /// A server-side error that may go away if the request is retried.
+    Transient { status: u16 },
+    /// Any other unexpected status code, with the start of the body.
+    Unexpected { status: u16, excerpt: String },
+}
+
+impl AocError {
+    /// Whether the request should be retried after a pause.
+    pub const fn is_transient(&self) -> bool { matches!(self, Self::Transient { .. }) }
+
+    /// A short suggestion for how to recover from this error.
+    pub const fn hint(&self) -> &'static str {
+        match self {
+            Self::InvalidSession { .. } | Self::LoginPage => {
+                "log in to adventofcode.com again, copy the session cookie and run `aocctl \
+                 auth set`, then `aocctl auth check`"
+            }
+            Self::NotUnlocked => {
+                "puzzles unlock at midnight UTC-5; check the year and day, or use `aocctl \
+                 input <day> --wait`"
+            }
+            Self::Transient { .. } => "the site is having trouble; try again in a few minutes",
+            Self::Unexpected { .. } => "check the year and day, then try again",
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSession { status } => {
+                write!(f, "HTTP {status}: the session token was not accepted")?
+            }
+            Self::NotUnlocked => write!(f, "HTTP 404: this puzzle is not available yet")?,
+            Self::LoginPage => write!(f, "received a login page instead of puzzle input")?,
+            Self::Transient { status } => write!(f, "HTTP {status}: server error")?,
+            Self::Unexpected { status, excerpt } if excerpt.is_empty() => {
+                write!(f, "HTTP {status}: unexpected response")?
+            }
+            Self::Unexpected { status, excerpt } => {
+                write!(f, "HTTP {status}: unexpected response: {excerpt}")?
+            }
+        }
+        write!(f, "\nhint: {}", self.hint())
+    }
+}
+
+impl std::error::Error for AocError {}
+
+/// Classify a response by status code and body.
+pub fn classify(status: u16, body: &str, expect: Expect) -> Result<(), AocError> {
+    match status {
+        200..=299 if expect == Expect::Text && looks_like_html(body) => {
+            Err(AocError::LoginPage)
+        }
+        200..=299 => Ok(()),
+        400 | 500 if body.contains(SESSION_REJECTED) => {
+            Err(AocError::InvalidSession { status })
+        }
+        404 => Err(AocError::NotUnlocked),
+        500..=599 => Err(AocError::Transient { status }),
+        _ => Err(AocError::Unexpected {
+            status,
+            excerpt: excerpt(body),
+        }),
+    }
+}
+
+/// The first 80 characters of `body`, on one line.
+fn excerpt(body: &str) -> String {
+    let line = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(80) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line,
+    }
+}
+
+fn looks_like_html(body: &str) -> bool {
+    let body = body.trim_start();
+    let head = body.get(..64).unwrap_or(body).to_ascii_lowercase();
+    head.starts_with("<!doctype html") || head.starts_with("<html")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn success_passes() {
+        assert_eq!(classify(200, "1\n2\n", Expect::Text), Ok(()));
+        assert_eq!(classify(200, "<!DOCTYPE html>", Expect::Html), Ok(()));
+    }
+
+    #[test]
+    fn html_instead_of_input_is_login_page() {
+        let body = "<!DOCTYPE html>\n<html><body>[Log In]</body></html>";
+        assert_eq!(classify(200, body, Expect::Text), Err(AocError::LoginPage));
+    }
+
+    #[test]
+    fn session_rejections() {
+        let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+        assert_eq!(
+            classify(400, body, Expect::Text),
+            Err(AocError::InvalidSession { status: 400 })
+        );
+        assert_eq!(
+            classify(500, body, Expect::Text),
+            Err(AocError::InvalidSession { status: 500 })
+        );
+    }
+
+    #[test]
+    fn not_unlocked_and_server_errors() {
+        assert_eq!(classify(404, "", Expect::Text), Err(AocError::NotUnlocked));
+        let err = classify(503, "", Expect::Html).unwrap_err();
+        assert!(err.is_transient());
+        assert!(!AocError::NotUnlocked.is_transient());
+        assert_eq!(
+            classify(500, "Internal Server Error", Expect::Html),
+            Err(AocError::Transient { status: 500 })
+        );
+    }
+
+    #[test]
+    fn other_bad_requests_are_unexpected() {
+        assert_eq!(
+            classify(400, "Bad   Request\n", Expect::Html),
+            Err(AocError::Unexpected {
+                status:  400,
+                excerpt: "Bad Request".to_string(),
+            })
+        );
+        let err = classify(400, &"x".repeat(200), Expect::Text).unwrap_err();
+        assert!(err.to_string().starts_with(&format!(
+            "HTTP 400: unexpected response: {}…",
+            "x".repeat(80)
+        )));
+        assert!(!err.to_string().contains("auth set"));
+    }
+
+    #[test]
+    fn display_includes_hint() {
+        let text = AocError::NotUnlocked.to_string();
+        assert!(text.contains("hint: puzzles unlock"));
+    }
+}
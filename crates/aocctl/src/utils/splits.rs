@@ -0,0 +1,170 @@
+//! Personal split times: when each day's input was first downloaded and when
+//! each part was accepted.
+//!
+//! Kept in the profile state directory (`.aocctl/splits/{year}.json` by
+//! default) as unix seconds, so the elapsed time since the unlock instant can
+//! be computed later. Only instants in the event's December are recorded.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::utils::calendar;
+use crate::utils::profile::Profile;
+
+/// Recorded instants for one day, as unix seconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaySplits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<u64>,
+}
+
+impl DaySplits {
+    /// Time from the unlock of `year`/`day` to `instant`.
+    pub fn elapsed(year: u32, day: u8, instant: Option<u64>) -> Option<Duration> {
+        let at = UNIX_EPOCH + Duration::from_secs(instant?);
+        Some(
+            at.duration_since(calendar::unlock_time(year, day))
+                .unwrap_or_default(),
+        )
+    }
+}
+
+/// Split times for one year.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Splits {
+    #[serde(skip)]
+    path:     PathBuf,
+    pub days: BTreeMap<u8, DaySplits>,
+}
+
+impl Splits {
+    /// Load the splits for `year` of the active profile.
+    pub fn load(year: u32) -> Result<Self> {
+        Self::load_from(
+            &Profile::active()
+                .state_dir()
+                .join("splits")
+                .join(format!("{year}.json")),
+        )
+    }
+
+    /// Load the splits kept in `path`, or empty ones if it does not exist.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let mut splits: Self = if path.exists() {
+            let text = fs::read_to_string(path)?;
+            serde_json::from_str(&text)
+                .map_err(|e| anyhow!("Invalid splits file {}: {e}", path.display()))?
+        } else {
+            Self::default()
+        };
+        splits.path = path.to_path_buf();
+        Ok(splits)
+    }
+
+    /// Record that the input for `day` was downloaded at `time`, unless an
+    /// earlier download is already recorded.
+    pub fn record_input(&mut self, day: u8, time: SystemTime) -> Result<()> {
+        let slot = &mut self.days.entry(day).or_default().input;
+        Self::set_once(slot, time)?;
+        self.save()
+    }
+
+    /// Record that `part` of `day` was accepted at `time`, unless it already
+    /// was.
+    pub fn record_accepted(&mut self, day: u8, part: u8, time: SystemTime) -> Result<()> {
+        let entry = self.days.entry(day).or_default();
+        let slot = if part == 1 { &mut entry.part1 } else { &mut entry.part2 };
+        Self::set_once(slot, time)?;
+        self.save()
+    }
+
+    fn set_once(slot: &mut Option<u64>, time: SystemTime) -> Result<()> {
+        if slot.is_none() {
+            *slot = Some(time.duration_since(UNIX_EPOCH)?.as_secs());
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+}
+
+/// Record the first download of an input for the active profile.
+///
+/// Like [`record_accepted`], this only happens while the event runs: catching
+/// up on a past year would otherwise record times measured from an unlock
+/// long ago.
+pub fn record_input(year: u32, day: u8) -> Result<()> {
+    let now = SystemTime::now();
+    if !calendar::is_during_event(year, now) {
+        return Ok(());
+    }
+    Splits::load(year)?.record_input(day, now)
+}
+
+/// Record the acceptance of a part for the active profile, if the event is
+/// still running.
+pub fn record_accepted(year: u32, day: u8, part: u8) -> Result<()> {
+    let now = SystemTime::now();
+    if !calendar::is_during_event(year, now) {
+        return Ok(());
+    }
+    Splits::load(year)?.record_accepted(day, part, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_record_wins_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("splits/2025.json");
+        let unlock = calendar::unlock_time(2025, 3);
+
+        let mut splits = Splits::load_from(&path).unwrap();
+        splits
+            .record_input(3, unlock + Duration::from_secs(5))
+            .unwrap();
+        splits
+            .record_input(3, unlock + Duration::from_secs(500))
+            .unwrap();
+        splits
+            .record_accepted(3, 2, unlock + Duration::from_secs(3723))
+            .unwrap();
+
+        let day = Splits::load_from(&path).unwrap().days[&3];
+        assert_eq!(
+            DaySplits::elapsed(2025, 3, day.input),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(day.part1, None);
+        assert_eq!(
+            DaySplits::elapsed(2025, 3, day.part2),
+            Some(Duration::from_secs(3723))
+        );
+    }
+
+    #[test]
+    fn elapsed_before_unlock_is_zero() {
+        let before = calendar::unlock_time(2025, 1) - Duration::from_secs(10);
+        let secs = before.duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert_eq!(
+            DaySplits::elapsed(2025, 1, Some(secs)),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(DaySplits::elapsed(2025, 1, None), None);
+    }
+}
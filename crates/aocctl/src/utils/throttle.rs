@@ -0,0 +1,102 @@
+//! Request throttling shared by every `aocctl` process.
+//!
+//! Advent of Code asks automated tools to space out their requests. The time
+//! of the most recent request is kept in `.aocctl/throttle`, so the minimum
+//! gap holds across separate invocations, e.g. when `aocctl` runs from a
+//! script.
+
+use std::io::{Read, Seek, Write};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::prelude::*;
+use crate::utils::config::Config;
+
+/// Gap enforced when `request_gap_secs` is not configured.
+pub const DEFAULT_GAP: Duration = Duration::from_secs(3);
+
+/// Location of the throttle state file.
+pub fn state_path() -> PathBuf { Path::new(".aocctl").join("throttle") }
+
+/// Block until the configured gap since the previous request has passed.
+///
+/// The state file is locked exclusively while the slot is read and reserved,
+/// and the slot is reserved before sleeping, so concurrent invocations queue
+/// up behind each other instead of firing at the same instant.
+/// The notice about the wait goes to stderr, so it never mixes with `--json`
+/// or `--csv` output.
+pub fn wait() -> Result<()> {
+    let gap = Config::load()?
+        .request_gap_secs
+        .map_or(DEFAULT_GAP, Duration::from_secs);
+
+    let path = state_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)?;
+    file.lock()?;
+
+    let mut text = String::new();
+    file.read_to_string(&mut text)?;
+    let last = text.trim().parse::<u64>().ok().map(Duration::from_millis);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let slot = next_slot(now, last, gap);
+
+    file.set_len(0)?;
+    file.rewind()?;
+    file.write_all(slot.as_millis().to_string().as_bytes())?;
+    file.unlock()?;
+
+    let delay = slot.saturating_sub(now);
+    if !delay.is_zero() {
+        eprintln!(
+            "Throttling: waiting {:.1}s before contacting AoC",
+            delay.as_secs_f64()
+        );
+        thread::sleep(delay);
+    }
+
+    Ok(())
+}
+
+/// Earliest time (since the epoch) the next request may be sent.
+fn next_slot(now: Duration, last: Option<Duration>, gap: Duration) -> Duration {
+    match last {
+        Some(last) => now.max(last + gap),
+        None => now,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAP: Duration = Duration::from_secs(3);
+
+    #[test]
+    fn first_request_goes_out_immediately() {
+        let now = Duration::from_secs(100);
+        assert_eq!(next_slot(now, None, GAP), now);
+    }
+
+    #[test]
+    fn request_inside_gap_is_delayed() {
+        let now = Duration::from_secs(100);
+        let last = Some(Duration::from_secs(99));
+        assert_eq!(next_slot(now, last, GAP), Duration::from_secs(102));
+    }
+
+    #[test]
+    fn request_after_gap_is_not_delayed() {
+        let now = Duration::from_secs(100);
+        let last = Some(Duration::from_secs(90));
+        assert_eq!(next_slot(now, last, GAP), now);
+    }
+}
@@ -0,0 +1,94 @@
+//! Sanity checks applied to an answer before it is submitted.
+//!
+//! Both `submit` and `solve` go through [`check_answer`], so the rules for
+//! what looks like a real answer live in one place.
+
+/// Result of [`check_answer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnswerCheck {
+    /// Nothing unusual.
+    Ok,
+    /// Plausible but unlikely; ask before sending. Carries the reason.
+    Suspicious(String),
+    /// Certainly not an answer; never send. Carries the reason.
+    Invalid(String),
+}
+
+/// Integer limits a solver typically hits when a computation overflows or a
+/// "minimum so far" is never updated.
+const OVERFLOW_VALUES: [i128; 5] = [
+    i64::MAX as i128,
+    i64::MIN as i128,
+    u64::MAX as i128,
+    i32::MAX as i128,
+    u32::MAX as i128,
+];
+
+/// Classify `answer` as fine, suspicious or invalid.
+pub fn check_answer(answer: &str) -> AnswerCheck {
+    let answer = answer.trim();
+
+    if answer.is_empty() {
+        return AnswerCheck::Invalid("the answer is empty".to_string());
+    }
+    if answer.to_ascii_lowercase().contains("elapsed") {
+        return AnswerCheck::Invalid("it contains timing output (`Elapsed`)".to_string());
+    }
+    if answer.contains(char::is_whitespace) {
+        return AnswerCheck::Invalid("it contains whitespace".to_string());
+    }
+
+    let Ok(n) = answer.parse::<i128>() else {
+        return AnswerCheck::Ok;
+    };
+
+    if n == 0 {
+        AnswerCheck::Suspicious(
+            "0 is what the day templates return before a part is implemented".to_string(),
+        )
+    } else if OVERFLOW_VALUES.contains(&n) {
+        AnswerCheck::Suspicious(format!("{n} is an integer limit; did something overflow?"))
+    } else if n < 0 {
+        AnswerCheck::Suspicious("puzzle answers are almost never negative".to_string())
+    } else {
+        AnswerCheck::Ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_invalid(answer: &str) -> bool {
+        matches!(check_answer(answer), AnswerCheck::Invalid(_))
+    }
+
+    fn is_suspicious(answer: &str) -> bool {
+        matches!(check_answer(answer), AnswerCheck::Suspicious(_))
+    }
+
+    #[test]
+    fn accepts_ordinary_answers() {
+        for answer in ["15", "120", "ABCDEFG", "1,2,3", "-", " 42\n"] {
+            assert_eq!(check_answer(answer), AnswerCheck::Ok, "{answer:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_values() {
+        assert!(is_invalid(""));
+        assert!(is_invalid("   "));
+        assert!(is_invalid("12 34"));
+        assert!(is_invalid("Elapsed: 1.2ms"));
+        assert!(is_invalid("42(elapsed"));
+    }
+
+    #[test]
+    fn flags_placeholders_negatives_and_overflow() {
+        assert!(is_suspicious("0"));
+        assert!(is_suspicious("-7"));
+        assert!(is_suspicious("9223372036854775807"));
+        assert!(is_suspicious("18446744073709551615"));
+        assert!(is_suspicious("2147483647"));
+    }
+}
@@ -0,0 +1,178 @@
+//! Interpretation of the page the site returns after submitting an answer.
+//!
+//! The answer endpoint always responds with `200 OK`; the outcome is only in
+//! the wording of the `<article>` block. [`SubmissionVerdict::parse`] maps
+//! the known phrasings onto a typed verdict.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of submitting an answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionVerdict {
+    /// The answer was accepted.
+    Correct,
+    /// The answer was wrong, with no hint about its size.
+    Incorrect,
+    /// The answer was wrong and too high.
+    TooHigh,
+    /// The answer was wrong and too low.
+    TooLow,
+    /// An answer was submitted too recently. `wait` is how long the site
+    /// asked to wait, if it said.
+    RateLimited { wait: Option<Duration> },
+    /// The part was already solved or is not open yet.
+    WrongLevel,
+    /// A response that did not match any known phrasing, with its text.
+    Unknown(String),
+}
+
+impl SubmissionVerdict {
+    /// Classify the text of the response `<article>`.
+    pub fn parse(text: &str) -> Self {
+        if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("answer is too high") {
+            Self::TooHigh
+        } else if text.contains("answer is too low") {
+            Self::TooLow
+        } else if text.contains("That's not the right answer") {
+            Self::Incorrect
+        } else if text.contains("You gave an answer too recently") {
+            Self::RateLimited {
+                wait: parse_wait(text),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown(text.trim().to_string())
+        }
+    }
+
+    /// Process exit status that `aocctl submit` reports for this verdict.
+    ///
+    /// `1` is left for ordinary errors and `2` for usage errors.
+    pub const fn exit_code(&self) -> u8 {
+        match self {
+            Self::Correct => 0,
+            Self::Incorrect => 10,
+            Self::TooHigh => 11,
+            Self::TooLow => 12,
+            Self::RateLimited { .. } => 13,
+            Self::WrongLevel => 14,
+            Self::Unknown(_) => 15,
+        }
+    }
+}
+
+impl fmt::Display for SubmissionVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect => write!(f, "incorrect"),
+            Self::TooHigh => write!(f, "incorrect (too high)"),
+            Self::TooLow => write!(f, "incorrect (too low)"),
+            Self::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited ({}s left)", wait.as_secs())
+            }
+            Self::RateLimited { wait: None } => write!(f, "rate limited"),
+            Self::WrongLevel => write!(f, "wrong level (already solved?)"),
+            Self::Unknown(_) => write!(f, "unrecognised response"),
+        }
+    }
+}
+
+/// Parse the `You have 1m 3s left to wait` part of a rate-limit message.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let rest = &text[start..];
+    let spec = &rest[..rest.find(" left to wait")?];
+
+    let mut secs = 0;
+    for part in spec.split_whitespace() {
+        let unit = part.chars().last()?;
+        let n: u64 = part[..part.len() - unit.len_utf8()].parse().ok()?;
+        secs += n * match unit {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_answer_outcomes() {
+        let parse = SubmissionVerdict::parse;
+        assert_eq!(
+            parse("That's the right answer!  You are one gold star closer."),
+            SubmissionVerdict::Correct
+        );
+        assert_eq!(
+            parse("That's not the right answer.  If you're stuck, ..."),
+            SubmissionVerdict::Incorrect
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too high."),
+            SubmissionVerdict::TooHigh
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too low."),
+            SubmissionVerdict::TooLow
+        );
+        assert_eq!(
+            parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            SubmissionVerdict::WrongLevel
+        );
+        assert_eq!(
+            parse("  Something new  "),
+            SubmissionVerdict::Unknown("Something new".to_string())
+        );
+    }
+
+    #[test]
+    fn rate_limit_carries_the_wait() {
+        let text = "You gave an answer too recently; you have to wait after submitting an \
+                    answer before trying again.  You have 1m 3s left to wait.";
+        assert_eq!(
+            SubmissionVerdict::parse(text),
+            SubmissionVerdict::RateLimited {
+                wait: Some(Duration::from_secs(63)),
+            }
+        );
+
+        let text = "You gave an answer too recently.";
+        assert_eq!(
+            SubmissionVerdict::parse(text),
+            SubmissionVerdict::RateLimited { wait: None }
+        );
+    }
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let verdicts = [
+            SubmissionVerdict::Correct,
+            SubmissionVerdict::Incorrect,
+            SubmissionVerdict::TooHigh,
+            SubmissionVerdict::TooLow,
+            SubmissionVerdict::RateLimited { wait: None },
+            SubmissionVerdict::WrongLevel,
+            SubmissionVerdict::Unknown(String::new()),
+        ];
+        let mut codes: Vec<u8> = verdicts.iter().map(SubmissionVerdict::exit_code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), verdicts.len());
+        assert!(!codes[1..].iter().any(|c| *c <= 2));
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! These tests focus on argument parsing and do not hit the network.
 
-use aocctl::{Cli, Command};
+use aocctl::{AnswersCommand, AuthCommand, Cli, Command};
 use clap::Parser;
 
 #[test]
@@ -26,6 +26,19 @@ fn parse_run_with_year_flag() {
     }
 }
 
+#[test]
+fn parse_input_with_force() {
+    let cli = Cli::parse_from(["aocctl", "input", "7", "--force"]);
+    match cli.command {
+        Command::Input { day, force, wait } => {
+            assert_eq!(day, 7);
+            assert!(force);
+            assert!(!wait);
+        }
+        other => panic!("Expected Input, got {other:?}"),
+    }
+}
+
 #[test]
 fn parse_submit_with_dry_run() {
     let cli = Cli::parse_from([
@@ -46,10 +59,14 @@ fn parse_submit_with_dry_run() {
             part,
             answer,
             dry_run,
+            force,
+            wait,
         } => {
             assert_eq!(day, 4);
             assert_eq!(part, 1);
             assert!(dry_run);
+            assert!(!force);
+            assert!(!wait);
             assert_eq!(answer.as_deref(), Some("12345"));
         }
         other => panic!("Expected Submit, got {other:?}"),
@@ -64,12 +81,179 @@ fn parse_solve_defaults() {
             day,
             ref input,
             dry_run,
+            force,
             ..
         } => {
             assert_eq!(day, 5);
             assert_eq!(input, "puzzle");
             assert!(!dry_run);
+            assert!(!force);
         }
         other => panic!("Expected Solve, got {other:?}"),
     }
 }
+
+#[test]
+fn parse_auth_set_with_token() {
+    let cli = Cli::parse_from(["aocctl", "auth", "set", "abc123"]);
+    match cli.command {
+        Command::Auth {
+            action: AuthCommand::Set { token },
+        } => assert_eq!(token.as_deref(), Some("abc123")),
+        other => panic!("Expected Auth Set, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_sync_with_pages() {
+    let cli = Cli::parse_from(["aocctl", "sync", "2024", "--pages"]);
+    match cli.command {
+        Command::Sync { year, pages } => {
+            assert_eq!(year, Some(2024));
+            assert!(pages);
+        }
+        other => panic!("Expected Sync, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_auth_import_with_cookie_path() {
+    let cli = Cli::parse_from([
+        "aocctl",
+        "auth",
+        "import",
+        "--browser",
+        "chromium",
+        "--cookies",
+        "Cookies",
+    ]);
+    match cli.command {
+        Command::Auth {
+            action: AuthCommand::Import { browser, cookies },
+        } => {
+            assert_eq!(format!("{browser:?}"), "Chromium");
+            assert_eq!(cookies.as_deref(), Some(std::path::Path::new("Cookies")));
+        }
+        other => panic!("Expected Auth Import, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_history_with_and_without_day() {
+    let cli = Cli::parse_from(["aocctl", "history"]);
+    assert!(matches!(cli.command, Command::History { day: None }));
+
+    let cli = Cli::parse_from(["aocctl", "history", "3"]);
+    assert!(matches!(cli.command, Command::History { day: Some(3) }));
+}
+
+#[test]
+fn parse_status_for_a_day_or_the_year() {
+    let cli = Cli::parse_from(["aocctl", "status", "4"]);
+    assert!(matches!(cli.command, Command::Status {
+        day: Some(4),
+        json: false,
+        ..
+    }));
+
+    let cli = Cli::parse_from(["aocctl", "status", "--json"]);
+    assert!(matches!(cli.command, Command::Status {
+        day:       None,
+        all_years: false,
+        json:      true,
+    }));
+
+    let cli = Cli::parse_from(["aocctl", "status", "--all-years"]);
+    assert!(matches!(cli.command, Command::Status {
+        day: None,
+        all_years: true,
+        ..
+    }));
+    assert!(Cli::try_parse_from(["aocctl", "status", "3", "--all-years"]).is_err());
+}
+
+#[test]
+fn parse_splits_with_optional_year() {
+    let cli = Cli::parse_from(["aocctl", "splits"]);
+    assert!(matches!(cli.command, Command::Splits { year: None }));
+
+    let cli = Cli::parse_from(["aocctl", "splits", "2024"]);
+    assert!(matches!(cli.command, Command::Splits { year: Some(2024) }));
+}
+
+#[test]
+fn parse_leaderboard_with_file() {
+    let cli = Cli::parse_from(["aocctl", "leaderboard", "123456", "--file", "board.json"]);
+    match cli.command {
+        Command::Leaderboard {
+            id,
+            file,
+            watch,
+            stats,
+            ..
+        } => {
+            assert_eq!(id, 123456);
+            assert_eq!(file.as_deref(), Some(std::path::Path::new("board.json")));
+            assert!(!watch && !stats);
+        }
+        other => panic!("Expected Leaderboard, got {other:?}"),
+    }
+
+    let cli = Cli::parse_from(["aocctl", "leaderboard", "1", "--watch"]);
+    assert!(matches!(cli.command, Command::Leaderboard {
+        watch: true,
+        ..
+    }));
+}
+
+#[test]
+fn parse_leaderboard_stats_options() {
+    let cli = Cli::parse_from([
+        "aocctl",
+        "leaderboard",
+        "1",
+        "--stats",
+        "--csv",
+        "--exclude-days",
+        "1,6",
+    ]);
+    match cli.command {
+        Command::Leaderboard {
+            stats,
+            csv,
+            exclude_days,
+            ..
+        } => {
+            assert!(stats && csv);
+            assert_eq!(exclude_days, [1, 6]);
+        }
+        other => panic!("Expected Leaderboard, got {other:?}"),
+    }
+
+    assert!(Cli::try_parse_from(["aocctl", "leaderboard", "1", "--csv"]).is_err());
+    assert!(Cli::try_parse_from(["aocctl", "leaderboard", "1", "--stats", "--watch"]).is_err());
+}
+
+#[test]
+fn parse_answers_pull_day_or_all() {
+    let cli = Cli::parse_from(["aocctl", "answers", "pull", "2024"]);
+    match cli.command {
+        Command::Answers {
+            action: AnswersCommand::Pull { year, days },
+        } => {
+            assert_eq!(year, Some(2024));
+            assert_eq!(format!("{days:?}"), "All");
+        }
+        other => panic!("Expected Answers Pull, got {other:?}"),
+    }
+
+    let cli = Cli::parse_from(["aocctl", "answers", "pull", "2024", "7"]);
+    assert!(matches!(
+        cli.command,
+        Command::Answers {
+            action: AnswersCommand::Pull { days, .. }
+        } if format!("{days:?}") == "One(7)"
+    ));
+
+    assert!(Cli::try_parse_from(["aocctl", "answers", "pull", "2024", "seven"]).is_err());
+}
@@ -0,0 +1,1314 @@
+//! End-to-end tests that run the `aocctl` binary against a mock AoC server.
+//!
+//! Nothing here touches the real adventofcode.com: every command runs in a
+//! temporary workspace with `AOC_BASE_URL` pointing at [`support::MockAoc`].
+
+mod support;
+
+use std::io::{BufRead, BufReader};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use support::{MockAoc, Sandbox, output_text};
+
+/// Write a tiny `aoc2025` crate whose day 1 sums and multiplies its input.
+fn scaffold_day_one(sandbox: &Sandbox) {
+    sandbox.write(
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\nresolver = \"3\"\n",
+    );
+    sandbox.write(
+        "crates/aoc2025/Cargo.toml",
+        "[package]\nname = \"aoc2025\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+    );
+    sandbox.write("crates/aoc2025/src/lib.rs", "");
+    sandbox.write(
+        "crates/aoc2025/src/bin/d01.rs",
+        r#"use std::io::Read;
+
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    let nums: Vec<i64> = input.lines().map(|l| l.parse().unwrap()).collect();
+    println!("Part 1: {}", nums.iter().sum::<i64>());
+    println!("Part 2: {}", nums.iter().product::<i64>());
+}
+"#,
+    );
+}
+
+#[test]
+fn input_downloads_with_session_cookie() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "input", "1"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+
+    assert_eq!(
+        sandbox.read("inputs/2025/d01.txt"),
+        support::INPUT.trim_end()
+    );
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/2025/day/1/input");
+    assert_eq!(
+        requests[0].cookie.as_deref(),
+        Some(format!("session={}", support::SESSION).as_str())
+    );
+}
+
+#[test]
+fn input_without_valid_session_fails() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox
+        .aocctl()
+        .env("SESSION", "not-the-right-token")
+        .args(["--year", "2025", "input", "1"])
+        .output()
+        .unwrap();
+
+    assert!(!out.status.success());
+    assert!(output_text(&out).contains("session token was not accepted"));
+    assert!(!sandbox.path().join("inputs/2025/d01.txt").exists());
+}
+
+#[test]
+fn submit_with_bad_session_fails() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox
+        .aocctl()
+        .env("SESSION", "not-the-right-token")
+        .args(["--year", "2025", "submit", "1", "--part", "1", "15"])
+        .output()
+        .unwrap();
+
+    assert!(!out.status.success());
+    assert!(output_text(&out).contains("auth set"));
+    assert!(!sandbox.path().join("answers/2025/ledger.json").exists());
+}
+
+#[test]
+fn input_retries_transient_server_errors() {
+    let mock = MockAoc::start();
+    mock.fail_next(1);
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "input", "1"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("HTTP 503"), "{text}");
+    assert!(text.contains("retrying"), "{text}");
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn input_for_locked_day_reports_not_unlocked() {
+    let mock = MockAoc::start();
+    mock.lock_from(2);
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "input", "2"], "");
+    let text = output_text(&out);
+    assert!(!out.status.success());
+    assert!(text.contains("not available yet"), "{text}");
+    assert!(text.contains("hint:"), "{text}");
+    assert_eq!(mock.requests().len(), 1, "404 must not be retried");
+}
+
+#[test]
+fn input_wait_retries_until_the_server_catches_up() {
+    let mock = MockAoc::start();
+    mock.lag_next(1);
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "input", "1", "--wait"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("Not available yet"), "{text}");
+    assert_eq!(
+        sandbox.read("inputs/2025/d01.txt"),
+        support::INPUT.trim_end()
+    );
+}
+
+#[test]
+fn input_rejects_days_outside_the_event() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "input", "13", "--wait"], "");
+    assert!(!out.status.success());
+    assert!(output_text(&out).contains("between 1 and 12"));
+    assert!(mock.requests().is_empty());
+}
+
+#[test]
+fn offline_input_is_served_from_cache() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--offline", "--year", "2025", "input", "1"], "");
+    assert!(!out.status.success());
+    assert!(output_text(&out).contains("--offline"));
+    assert!(mock.requests().is_empty());
+
+    let out = sandbox.run(&["--year", "2025", "input", "1"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+    std::fs::remove_file(sandbox.path().join("inputs/2025/d01.txt")).unwrap();
+
+    let out = sandbox.run(&["--offline", "--year", "2025", "input", "1"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+    assert_eq!(
+        sandbox.read("inputs/2025/d01.txt"),
+        support::INPUT.trim_end()
+    );
+    assert_eq!(mock.requests().len(), 1);
+}
+
+#[test]
+fn cache_is_kept_per_site() {
+    let mock = MockAoc::start();
+    let other = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "input", "1"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+    std::fs::remove_file(sandbox.path().join("inputs/2025/d01.txt")).unwrap();
+
+    let out = sandbox
+        .aocctl()
+        .env("AOC_BASE_URL", other.base_url())
+        .args(["--year", "2025", "input", "1"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{}", output_text(&out));
+    assert_eq!(mock.requests().len(), 1);
+    assert_eq!(other.requests().len(), 1);
+}
+
+#[test]
+fn input_is_not_downloaded_twice_without_force() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    sandbox.run(&["--year", "2025", "input", "1"], "");
+    sandbox.write("inputs/2025/d01.txt", "edited");
+
+    let out = sandbox.run(&["--year", "2025", "input", "1"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+    assert!(output_text(&out).contains("--force"));
+    assert_eq!(sandbox.read("inputs/2025/d01.txt"), "edited");
+
+    let out = sandbox.run(&["--year", "2025", "input", "1", "--force"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+    assert_eq!(
+        sandbox.read("inputs/2025/d01.txt"),
+        support::INPUT.trim_end()
+    );
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn throttle_holds_across_invocations() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write(".aocctl/config.toml", "request_gap_secs = 1\n");
+
+    let start = Instant::now();
+    sandbox.run(&["--year", "2025", "input", "1"], "");
+    let out = sandbox.run(&["--year", "2025", "input", "1", "--force"], "");
+
+    assert!(
+        output_text(&out).contains("Throttling"),
+        "{}",
+        output_text(&out)
+    );
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn session_is_read_from_dotenv() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write(
+        ".env",
+        &format!("OTHER=1\nSESSION=\"{}\"\n", support::SESSION),
+    );
+    sandbox.write(".session", "stale-token");
+
+    let out = sandbox
+        .aocctl()
+        .env_remove("SESSION")
+        .args(["--year", "2025", "input", "1"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{}", output_text(&out));
+}
+
+#[test]
+fn session_is_read_from_home_config() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write(".config/aoc/.session", support::SESSION);
+
+    let out = sandbox
+        .aocctl()
+        .env_remove("SESSION")
+        .env_remove("XDG_CONFIG_HOME")
+        .args(["auth", "show"])
+        .output()
+        .unwrap();
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains(".config/aoc/.session"), "{text}");
+}
+
+#[test]
+fn missing_session_lists_every_location() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox
+        .aocctl()
+        .env_remove("SESSION")
+        .args(["auth", "show"])
+        .output()
+        .unwrap();
+    let text = output_text(&out);
+    assert!(!out.status.success());
+    for location in [
+        "environment variable SESSION",
+        "SESSION in .env",
+        ".config/aoc/.session",
+        "file .session",
+        "file config/.session",
+        "file aoc.session",
+    ] {
+        assert!(text.contains(location), "missing {location:?} in {text}");
+    }
+}
+
+#[test]
+fn profile_uses_its_own_session_and_inputs() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--profile", "work", "--year", "2025", "input", "1"], "");
+    assert!(
+        !out.status.success(),
+        "SESSION must not be used for a named profile"
+    );
+    assert!(output_text(&out).contains("SESSION_WORK"));
+
+    let out = sandbox
+        .aocctl()
+        .env_remove("SESSION")
+        .env("SESSION_WORK", support::SESSION)
+        .args(["--profile", "work", "--year", "2025", "input", "1"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{}", output_text(&out));
+    assert!(sandbox.path().join("inputs/work/2025/d01.txt").exists());
+    assert!(!sandbox.path().join("inputs/2025/d01.txt").exists());
+}
+
+#[test]
+fn submit_right_answer() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "15"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("That's the right answer!"), "{text}");
+
+    // Accepting part one shows part two and saves only its new example.
+    assert!(text.contains("--- Part Two ---"), "{text}");
+    assert!(
+        text.contains("Now multiply every number instead."),
+        "{text}"
+    );
+    assert!(
+        text.contains("Saved example to inputs/2025/d01.example1.txt"),
+        "{text}"
+    );
+    assert_eq!(sandbox.read("inputs/2025/d01.example1.txt"), "1\n2\n3\n4\n");
+    assert!(!sandbox.path().join("inputs/2025/d01.example2.txt").exists());
+
+    let post = mock
+        .requests()
+        .into_iter()
+        .find(|r| r.method == "POST")
+        .expect("answer was posted");
+    assert_eq!(post.path, "/2025/day/1/answer");
+    assert!(
+        mock.requests()
+            .iter()
+            .any(|r| r.method == "GET" && r.path == "/2025/day/1")
+    );
+    assert_eq!(post.body, "level=1&answer=15");
+}
+
+#[test]
+fn submit_wrong_and_too_high_answers() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "abc"], "");
+    assert!(output_text(&out).contains("That's not the right answer."));
+    assert_eq!(out.status.code(), Some(10));
+
+    let out = sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "99"], "");
+    assert!(output_text(&out).contains("your answer is too high"));
+    assert_eq!(out.status.code(), Some(11));
+
+    let out = sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "1"], "");
+    assert!(output_text(&out).contains("your answer is too low"));
+    assert_eq!(out.status.code(), Some(12));
+}
+
+#[test]
+fn history_lists_ledger_and_imported_logs() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write("answers/2025/d01.txt", "part 1: 42\n");
+
+    sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "99"], "");
+    sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "15"], "");
+    sandbox.run(
+        &[
+            "--year",
+            "2025",
+            "submit",
+            "1",
+            "--part",
+            "2",
+            "7",
+            "--dry-run",
+        ],
+        "",
+    );
+
+    let ledger = sandbox.read("answers/2025/ledger.json");
+    assert!(ledger.contains("\"too_high\""), "{ledger}");
+    assert!(sandbox.path().join("answers/2025/d01.txt").exists());
+
+    let out = sandbox.run(&["--year", "2025", "history", "1"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    let rows: Vec<&str> = text.lines().skip(1).collect();
+    assert_eq!(rows.len(), 4, "{text}");
+    assert!(rows[0].starts_with('-') && rows[0].contains("42"), "{text}");
+    assert!(rows[1].contains("incorrect (too high)"), "{text}");
+    assert!(rows[2].contains("correct"), "{text}");
+    assert!(rows[3].ends_with("dry run"), "{text}");
+
+    let out = sandbox.run(&["--year", "2025", "history", "2"], "");
+    assert!(output_text(&out).contains("No answers recorded for 2025 day 2"));
+}
+
+#[test]
+fn status_counts_stars_for_a_day_and_the_year() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    mock.solve(1, 2);
+    mock.solve(2, 1);
+    mock.lock_from(4);
+
+    let out = sandbox.run(&["--year", "2025", "status", "2"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("Stars for 2025 day 2: 1"), "{text}");
+    assert!(text.contains("Part 1 complete"), "{text}");
+
+    let out = sandbox.run(&["--year", "2025", "status"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("Stars for 2025: 3/24"), "{text}");
+    assert!(text.contains(" 1 **    2 *     3 -     4"), "{text}");
+    assert!(
+        text.lines()
+            .any(|l| l.starts_with("11") && l.trim_end() == "11      12"),
+        "{text}"
+    );
+
+    let calendar = mock
+        .requests()
+        .into_iter()
+        .find(|r| r.path == "/2025")
+        .expect("calendar was fetched");
+    assert_eq!(
+        calendar.cookie.as_deref(),
+        Some(format!("session={}", support::SESSION).as_str())
+    );
+
+    let out = sandbox.run(&["--year", "2025", "status", "--json"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+    let json: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(json["stars"], 3);
+    assert_eq!(json["days"].as_array().unwrap().len(), 12);
+    assert_eq!(json["days"][0]["stars"], 2);
+    assert_eq!(json["days"][2]["unlocked"], true);
+    assert_eq!(json["days"][3]["unlocked"], false);
+}
+
+#[test]
+fn status_all_years_cross_checks_local_solutions() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    mock.solve(1, 2);
+    mock.solve(2, 1);
+    sandbox.write("crates/aoc2025/src/bin/d01.rs", "fn main() {}\n");
+    sandbox.write("crates/aoc2025/src/bin/d03.rs", "fn main() {}\n");
+    sandbox.write("crates/aoc2024/src/bin/d01.rs", "fn main() {}\n");
+
+    let out = sandbox.run(&["status", "--all-years"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(
+        text.contains("2025   3/24  crates/aoc2025 (2 days)"),
+        "{text}"
+    );
+    assert!(
+        text.contains("2024   0/50  crates/aoc2024 (1 day)"),
+        "{text}"
+    );
+    assert!(text.contains("2015   0/50  -"), "{text}");
+    assert!(
+        text.contains("Days with stars but no local solution:\n  2025: day 2\n"),
+        "{text}"
+    );
+    assert!(
+        text.contains("Local solutions without stars:\n  2025: day 3\n  2024: day 1\n"),
+        "{text}"
+    );
+
+    // Only 2025 has stars, so no other calendar is fetched.
+    let calendars: Vec<String> = mock
+        .requests()
+        .into_iter()
+        .map(|r| r.path)
+        .filter(|p| p.len() == 5)
+        .collect();
+    assert_eq!(calendars, ["/2025"]);
+
+    let out = sandbox.run(&["status", "--all-years", "--json"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+    let json: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 11);
+    assert_eq!(json[0]["year"], 2025);
+    assert_eq!(json[0]["no_solution"], serde_json::json!([2]));
+    assert_eq!(json[1]["no_stars"], serde_json::json!([1]));
+}
+
+#[test]
+fn throttle_and_retry_notices_stay_out_of_machine_output() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write(".aocctl/config.toml", "request_gap_secs = 1\n");
+    mock.solve(1, 2);
+
+    // The events page and the 2025 calendar: the second request is throttled.
+    let out = sandbox.run(&["status", "--all-years", "--json"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("Throttling"), "{stderr}");
+    let json: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(json[0]["year"], 2025);
+
+    mock.fail_next(1);
+    let args = ["--year", "2025", "leaderboard", "42", "--stats", "--csv"];
+    let out = sandbox.run(&args, "");
+    assert!(out.status.success(), "{}", output_text(&out));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("Throttling"), "{stderr}");
+    assert!(stderr.contains("retrying"), "{stderr}");
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.starts_with("member_id,member,day,"), "{stdout}");
+    assert!(stdout.lines().skip(1).all(|l| l.split(',').count() == 9));
+}
+
+#[test]
+fn leaderboard_from_file_renders_table() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write("board.json", support::LEADERBOARD);
+
+    let out = sandbox.run(
+        &["--offline", "leaderboard", "42", "--file", "board.json"],
+        "",
+    );
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(
+        text.contains("Private leaderboard 42 for 2025 (3 members)"),
+        "{text}"
+    );
+    let rows: Vec<&str> = text.lines().skip(4).collect();
+    assert_eq!(rows[0], "  1)    14     5  **+.........  alice", "{text}");
+    assert_eq!(rows[1], "  2)     7     3  *+..........  bob", "{text}");
+    assert_eq!(
+        rows[2], "  3)     0     0  ............  (anonymous user #3)",
+        "{text}"
+    );
+    assert!(mock.requests().is_empty());
+}
+
+#[test]
+fn leaderboard_download_is_cached() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    for _ in 0..2 {
+        let out = sandbox.run(&["--year", "2025", "leaderboard", "42"], "");
+        let text = output_text(&out);
+        assert!(out.status.success(), "{text}");
+        assert!(text.contains("alice"), "{text}");
+    }
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/2025/leaderboard/private/view/42.json");
+
+    let out = sandbox
+        .aocctl()
+        .args(["--year", "2025", "leaderboard", "7"])
+        .env("SESSION", "not-the-right-token")
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(
+        output_text(&out).contains("login page"),
+        "{}",
+        output_text(&out)
+    );
+}
+
+#[test]
+fn leaderboard_stats_as_table_and_csv() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write("board.json", support::LEADERBOARD);
+    let args = ["leaderboard", "42", "--file", "board.json", "--stats"];
+
+    let out = sandbox.run(&args, "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(
+        text.contains("  1)    14    14  alice                1 1 1"),
+        "{text}"
+    );
+    assert!(
+        text.contains("  2)     7     7  bob                  1 2 2"),
+        "{text}"
+    );
+    assert!(
+        text.contains("alice                  2      00:03:20      00:16:40      00:13:20"),
+        "{text}"
+    );
+
+    let mut csv_args = args.to_vec();
+    csv_args.extend(["--csv", "--exclude-days", "1"]);
+    let out = sandbox.run(&csv_args, "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[0],
+        "member_id,member,day,part1_secs,part2_secs,delta_secs,points,score,rank"
+    );
+    assert_eq!(lines.len(), 1 + 3 * 3, "{text}");
+    assert!(lines.contains(&"1,alice,1,300,900,600,0,0,1"), "{text}");
+    assert!(lines.contains(&"1,alice,3,100,,,3,9,1"), "{text}");
+    assert!(lines.contains(&"2,bob,2,500,,,2,2,2"), "{text}");
+}
+
+/// Run a long-lived `aocctl` until it prints a line starting with `marker`,
+/// then stop it and return everything it printed.
+fn run_until(sandbox: &Sandbox, args: &[&str], marker: &str) -> String {
+    let mut child = sandbox
+        .aocctl()
+        .args(args)
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("spawn aocctl");
+
+    let mut text = String::new();
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.unwrap();
+        text.push_str(&line);
+        text.push('\n');
+        if line.starts_with(marker) {
+            break;
+        }
+    }
+    child.kill().ok();
+    child.wait().unwrap();
+    text
+}
+
+#[test]
+fn leaderboard_watch_reports_new_stars_once() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write(
+        ".aocctl/config.toml",
+        &format!(
+            "request_gap_secs = 0\nleaderboard_webhook = \"{}/webhook\"\n",
+            mock.base_url()
+        ),
+    );
+
+    // Start from a snapshot where bob has not solved day 2 and alice not day 3.
+    let mut board: serde_json::Value = serde_json::from_str(support::LEADERBOARD).unwrap();
+    board["members"]["2"]["completion_day_level"]
+        .as_object_mut()
+        .unwrap()
+        .remove("2");
+    board["members"]["1"]["completion_day_level"]
+        .as_object_mut()
+        .unwrap()
+        .remove("3");
+    sandbox.write("board.json", &board.to_string());
+
+    let args = ["leaderboard", "42", "--file", "board.json", "--watch"];
+    let text = run_until(&sandbox, &args, "Next check at");
+    assert!(
+        text.contains("Watching leaderboard 42 for 2025: 3 members"),
+        "{text}"
+    );
+    assert!(
+        sandbox
+            .path()
+            .join(".aocctl/leaderboards/2025-42.json")
+            .exists()
+    );
+
+    sandbox.write("board.json", support::LEADERBOARD);
+    let text = run_until(&sandbox, &args, "Next check at");
+    let events: Vec<&str> = text.lines().filter(|l| l.contains(" got ")).collect();
+    assert_eq!(events, [
+        "bob got day 2 part 1 (+2 pts)",
+        "alice got day 3 part 1 (+3 pts)"
+    ]);
+
+    let posts: Vec<_> = mock
+        .requests()
+        .into_iter()
+        .filter(|r| r.path == "/webhook")
+        .collect();
+    assert_eq!(posts.len(), 1);
+    let payload: serde_json::Value = serde_json::from_str(&posts[0].body).unwrap();
+    assert_eq!(payload["leaderboard"], 42);
+    assert_eq!(payload["events"][0]["member"], "bob");
+    assert_eq!(payload["events"][1]["points"], 3);
+    assert_eq!(
+        payload["events"][1]["message"],
+        "alice got day 3 part 1 (+3 pts)"
+    );
+
+    // A restarted watcher starts from the saved snapshot.
+    let text = run_until(&sandbox, &args, "Next check at");
+    assert!(text.contains("No new stars"), "{text}");
+    assert_eq!(mock.requests().len(), 1);
+}
+
+#[test]
+fn leaderboard_watch_resends_stars_after_a_failed_webhook() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write(
+        ".aocctl/config.toml",
+        &format!(
+            "request_gap_secs = 0\nleaderboard_webhook = \"{}/webhook\"\n",
+            mock.base_url()
+        ),
+    );
+
+    let mut board: serde_json::Value = serde_json::from_str(support::LEADERBOARD).unwrap();
+    board["members"]["1"]["completion_day_level"]
+        .as_object_mut()
+        .unwrap()
+        .remove("3");
+    sandbox.write("board.json", &board.to_string());
+    let args = ["leaderboard", "42", "--file", "board.json", "--watch"];
+    run_until(&sandbox, &args, "Next check at");
+    let snapshot = sandbox.read(".aocctl/leaderboards/2025-42.json");
+
+    // The webhook fails, so the snapshot stays put and the star is resent.
+    sandbox.write("board.json", support::LEADERBOARD);
+    mock.fail_next(1);
+    let text = run_until(&sandbox, &args, "Next check at");
+    assert!(text.contains("alice got day 3 part 1"), "{text}");
+    assert_eq!(sandbox.read(".aocctl/leaderboards/2025-42.json"), snapshot);
+
+    let text = run_until(&sandbox, &args, "Next check at");
+    assert!(text.contains("alice got day 3 part 1"), "{text}");
+    assert_ne!(sandbox.read(".aocctl/leaderboards/2025-42.json"), snapshot);
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn leaderboard_watch_stops_on_a_rejected_session() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox
+        .aocctl()
+        .env("SESSION", "not-the-right-token")
+        .args(["--year", "2025", "leaderboard", "42", "--watch"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let text = output_text(&out);
+    assert!(text.contains("login page"), "{text}");
+    assert!(!text.contains("Next check at"), "{text}");
+}
+
+#[test]
+fn splits_are_only_recorded_during_the_event() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "splits"], "");
+    assert!(output_text(&out).contains("No split times recorded for 2025"));
+
+    // 2025 is over, so catching up on it records nothing.
+    sandbox.run(&["--year", "2025", "input", "1"], "");
+    sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "15"], "");
+    assert!(!sandbox.path().join(".aocctl/splits/2025.json").exists());
+
+    // Input 12s and part one 1h 2m 3s after the 2025-12-01 unlock.
+    sandbox.write(
+        ".aocctl/splits/2025.json",
+        r#"{ "days": { "1": { "input": 1764565212, "part1": 1764568923 } } }"#,
+    );
+    let out = sandbox.run(&["--year", "2025", "splits"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    let row = text
+        .lines()
+        .find(|line| line.trim_start().starts_with("1 "))
+        .expect("a row for day 1");
+    let cells: Vec<&str> = row.split("  ").filter(|c| !c.is_empty()).collect();
+    assert_eq!(cells.len(), 5, "{text}");
+    assert_eq!(cells[1].trim(), "00:00:12", "{text}");
+    assert_eq!(cells[2].trim(), "01:02:03", "{text}");
+    assert_eq!(cells[3].trim(), "-", "{text}");
+    assert_eq!(cells[4].trim(), "-", "{text}");
+}
+
+#[test]
+fn submit_refuses_known_wrong_answers_unless_forced() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    let posts = || {
+        mock.requests()
+            .iter()
+            .filter(|r| r.method == "POST")
+            .count()
+    };
+
+    sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "20"], "");
+    assert_eq!(posts(), 1);
+
+    for answer in ["20", "25"] {
+        let out = sandbox.run(
+            &["--year", "2025", "submit", "1", "--part", "1", answer],
+            "",
+        );
+        let text = output_text(&out);
+        assert_eq!(out.status.code(), Some(1), "{text}");
+        assert!(text.contains("--force"), "{text}");
+    }
+    assert_eq!(posts(), 1);
+
+    let out = sandbox.run(
+        &[
+            "--year", "2025", "submit", "1", "--part", "1", "25", "--force",
+        ],
+        "",
+    );
+    assert_eq!(out.status.code(), Some(11), "{}", output_text(&out));
+    assert_eq!(posts(), 2);
+}
+
+#[test]
+fn submit_guards_against_placeholder_and_malformed_answers() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    let posts = || {
+        mock.requests()
+            .iter()
+            .filter(|r| r.method == "POST")
+            .count()
+    };
+
+    let out = sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "0"], "\n");
+    let text = output_text(&out);
+    assert_eq!(out.status.code(), Some(1), "{text}");
+    assert!(text.contains("day templates return"), "{text}");
+    assert_eq!(posts(), 0);
+
+    let out = sandbox.run(
+        &[
+            "--year",
+            "2025",
+            "submit",
+            "1",
+            "--part",
+            "1",
+            "Elapsed: 3ms",
+            "--force",
+        ],
+        "",
+    );
+    assert!(output_text(&out).contains("Refusing to submit"));
+    assert_eq!(posts(), 0);
+
+    let out = sandbox.run(
+        &["--year", "2025", "submit", "1", "--part", "1", "0"],
+        "yes\n",
+    );
+    assert_eq!(out.status.code(), Some(12), "{}", output_text(&out));
+    assert_eq!(posts(), 1);
+
+    // A dry run reports the warning instead of asking.
+    let out = sandbox.run(
+        &[
+            "--year",
+            "2025",
+            "submit",
+            "1",
+            "--part",
+            "2",
+            "0",
+            "--dry-run",
+        ],
+        "",
+    );
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("day templates return"), "{text}");
+    assert!(!text.contains("Type `yes` to confirm"), "{text}");
+    assert_eq!(posts(), 1);
+
+    // Surrounding whitespace is trimmed before the answer is sent.
+    sandbox.run(
+        &["--year", "2025", "submit", "1", "--part", "1", " 15\n"],
+        "",
+    );
+    let post = mock
+        .requests()
+        .into_iter()
+        .rfind(|r| r.method == "POST")
+        .unwrap();
+    assert_eq!(post.body, "level=1&answer=15");
+    let ledger = sandbox.read("answers/2025/ledger.json");
+    assert!(ledger.contains("\"15\""), "{ledger}");
+    assert!(!ledger.contains(" 15"), "{ledger}");
+}
+
+#[test]
+fn submit_rate_limited() {
+    let mock = MockAoc::start();
+    mock.set_rate_limited(true);
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "15"], "");
+    let text = output_text(&out);
+    assert!(text.contains("You have 43s left to wait"), "{text}");
+    assert!(text.contains("Retry allowed at"), "{text}");
+    assert_eq!(out.status.code(), Some(13));
+}
+
+#[test]
+fn submit_is_not_retried_after_a_server_error() {
+    let mock = MockAoc::start();
+    mock.fail_next(1);
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["--year", "2025", "submit", "1", "--part", "1", "15"], "");
+    let text = output_text(&out);
+    assert!(!out.status.success(), "{text}");
+    assert!(text.contains("HTTP 503"), "{text}");
+    assert!(text.contains("aocctl --year 2025 status 1"), "{text}");
+    assert!(!text.contains("retrying"), "{text}");
+    let posts = mock
+        .requests()
+        .iter()
+        .filter(|r| r.method == "POST")
+        .count();
+    assert_eq!(posts, 1);
+}
+
+#[test]
+fn submit_wait_resubmits_after_rate_limit() {
+    let mock = MockAoc::start();
+    mock.rate_limit_next(1);
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(
+        &[
+            "--year", "2025", "submit", "1", "--part", "1", "15", "--wait",
+        ],
+        "",
+    );
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("Resubmitting in"), "{text}");
+    assert!(text.contains("That's the right answer!"), "{text}");
+
+    let posts = mock
+        .requests()
+        .iter()
+        .filter(|r| r.method == "POST")
+        .count();
+    assert_eq!(posts, 2);
+}
+
+#[test]
+fn submit_offline_sends_nothing() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(
+        &[
+            "--offline",
+            "--year",
+            "2025",
+            "submit",
+            "1",
+            "--part",
+            "1",
+            "15",
+        ],
+        "",
+    );
+    assert!(!out.status.success());
+    assert!(mock.requests().is_empty());
+}
+
+#[test]
+fn solve_runs_day_and_submits_chosen_part() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    scaffold_day_one(&sandbox);
+
+    let out = sandbox.run(&["--year", "2025", "input", "1"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+
+    let out = sandbox.run(&["--year", "2025", "solve", "1", "--mode", "debug"], "2\n");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("Part 2 = Some(\"120\")"), "{text}");
+    assert!(text.contains("That's the right answer!"), "{text}");
+
+    let post = mock
+        .requests()
+        .into_iter()
+        .find(|r| r.method == "POST")
+        .expect("answer was posted");
+    assert_eq!(post.body, "level=2&answer=120");
+}
+
+#[test]
+fn answers_pull_records_accepted_answers() {
+    let mock = MockAoc::start();
+    mock.solve(1, 2);
+    mock.solve(2, 1);
+    mock.lock_from(4);
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox.run(&["answers", "pull", "2025"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("Day  1: part 1 = 15, part 2 = 120"), "{text}");
+    assert!(text.contains("Day  2: part 1 = 15"), "{text}");
+    assert!(text.contains("Day  3: not solved yet"), "{text}");
+    assert!(text.contains("Imported 3 accepted answers"), "{text}");
+
+    let out = sandbox.run(&["answers", "pull", "2025", "1"], "");
+    let text = output_text(&out);
+    assert!(
+        text.contains("Day  1: part 1 known, part 2 known"),
+        "{text}"
+    );
+    assert!(text.contains("Imported 0 accepted answers"), "{text}");
+
+    let out = sandbox.run(&["--year", "2025", "history", "1"], "");
+    let text = output_text(&out);
+    assert_eq!(text.matches("correct").count(), 2, "{text}");
+}
+
+#[test]
+fn solve_auto_submits_first_unsolved_part() {
+    let mock = MockAoc::start();
+    mock.solve(1, 1);
+    let sandbox = Sandbox::new(&mock);
+    scaffold_day_one(&sandbox);
+    sandbox.run(&["--year", "2025", "input", "1"], "");
+
+    let args = ["--year", "2025", "solve", "1", "--mode", "debug", "--auto"];
+    let out = sandbox.run(&args, "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("Part 2 is the first unsolved part"), "{text}");
+    assert!(text.contains("That's the right answer!"), "{text}");
+
+    let posts: Vec<_> = mock
+        .requests()
+        .into_iter()
+        .filter(|r| r.method == "POST")
+        .collect();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].body, "level=2&answer=120");
+
+    let out = sandbox.run(&args, "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(
+        text.contains("Both parts of 2025 day 1 are already solved"),
+        "{text}"
+    );
+    assert!(!text.contains("Solver output"), "{text}");
+}
+
+#[test]
+fn solve_auto_does_not_trust_a_cached_day_page() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    scaffold_day_one(&sandbox);
+    sandbox.run(&["--year", "2025", "input", "1"], "");
+
+    // Cache the page while nothing is solved, then solve part one elsewhere.
+    let out = sandbox.run(&["--year", "2025", "status", "1"], "");
+    assert!(output_text(&out).contains("No parts completed yet"));
+    mock.solve(1, 1);
+
+    let args = ["--year", "2025", "solve", "1", "--mode", "debug", "--auto"];
+    let mut offline = vec!["--offline"];
+    offline.extend(args);
+    let out = sandbox.run(&offline, "");
+    let text = output_text(&out);
+    assert!(!out.status.success(), "{text}");
+    assert!(text.contains("--offline"), "{text}");
+
+    let out = sandbox.run(&args, "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(text.contains("Part 2 is the first unsolved part"), "{text}");
+    let post = mock
+        .requests()
+        .into_iter()
+        .find(|r| r.method == "POST")
+        .expect("answer was posted");
+    assert_eq!(post.body, "level=2&answer=120");
+}
+
+#[test]
+fn solve_auto_refuses_suspicious_answers_without_prompting() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+    scaffold_day_one(&sandbox);
+    sandbox.write(
+        "crates/aoc2025/src/bin/d01.rs",
+        "fn main() {\n    println!(\"Part 1: 0\");\n}\n",
+    );
+    sandbox.run(&["--year", "2025", "input", "1"], "");
+
+    // Even a `yes` on stdin must not be read as a confirmation.
+    let args = ["--year", "2025", "solve", "1", "--mode", "debug", "--auto"];
+    let out = sandbox.run(&args, "yes\n");
+    let text = output_text(&out);
+    assert!(!out.status.success(), "{text}");
+    assert!(
+        text.contains("Not submitting suspicious answer 0"),
+        "{text}"
+    );
+    assert!(text.contains("pass --force"), "{text}");
+    assert!(!text.contains("Type `yes` to confirm"), "{text}");
+    assert!(!mock.requests().iter().any(|r| r.method == "POST"));
+
+    let args = [
+        "--year", "2025", "solve", "1", "--mode", "debug", "--auto", "--force",
+    ];
+    let text = output_text(&sandbox.run(&args, ""));
+    assert!(
+        text.contains("submitting anyway because of --force"),
+        "{text}"
+    );
+    assert_eq!(
+        mock.requests()
+            .iter()
+            .filter(|r| r.method == "POST")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn hashed_answers_are_used_for_comparisons() {
+    let mock = MockAoc::start();
+    mock.solve(1, 1);
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write(
+        ".aocctl/config.toml",
+        "request_gap_secs = 0\nhash_answers = true\nhash_iterations = 1000\n",
+    );
+    scaffold_day_one(&sandbox);
+
+    let out = sandbox.run(&["answers", "pull", "2025", "1"], "");
+    assert!(out.status.success(), "{}", output_text(&out));
+
+    let hashes = sandbox.read("answer-hashes/2025.json");
+    assert!(hashes.contains("\"01/1\""), "{hashes}");
+    assert!(hashes.contains("\"iterations\": 1000"), "{hashes}");
+    assert!(
+        !hashes.contains(&format!("\"{}\"", support::ANSWERS[0])),
+        "{hashes}"
+    );
+
+    // Only the hashes are committed; comparisons must work without answers/.
+    std::fs::remove_dir_all(sandbox.path().join("answers")).unwrap();
+    sandbox.run(&["--year", "2025", "input", "1"], "");
+
+    let out = sandbox.run(&["--year", "2025", "solve", "1", "--mode", "debug"], "\n");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(
+        text.contains("✓ Part 1 matches the accepted answer"),
+        "{text}"
+    );
+    assert!(!text.contains("Part 2 matches"), "{text}");
+}
+
+#[test]
+fn auth_set_show_and_check() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox
+        .aocctl()
+        .env_remove("SESSION")
+        .args(["auth", "set", support::SESSION])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{}", output_text(&out));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        let meta = std::fs::metadata(sandbox.path().join(".session")).unwrap();
+        assert_eq!(meta.permissions().mode() & 0o777, 0o600);
+    }
+
+    let out = sandbox
+        .aocctl()
+        .env_remove("SESSION")
+        .args(["auth", "show"])
+        .output()
+        .unwrap();
+    let text = output_text(&out);
+    assert!(text.contains("file .session"), "{text}");
+    assert!(!text.contains(support::SESSION), "{text}");
+
+    let out = sandbox
+        .aocctl()
+        .env_remove("SESSION")
+        .args(["auth", "check"])
+        .output()
+        .unwrap();
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+    assert!(
+        text.contains(&format!("Logged in as {}", support::USER_NAME)),
+        "{text}"
+    );
+}
+
+#[test]
+fn auth_check_rejects_bad_token() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    let out = sandbox
+        .aocctl()
+        .env("SESSION", "deadbeef")
+        .args(["auth", "check"])
+        .output()
+        .unwrap();
+
+    assert!(!out.status.success());
+    assert!(output_text(&out).contains("not accepted"));
+}
+
+#[test]
+fn auth_import_reads_firefox_profile() {
+    let mock = MockAoc::start();
+    let sandbox = Sandbox::new(&mock);
+
+    sandbox.write(
+        ".mozilla/firefox/profiles.ini",
+        "[Profile0]\nName=default\nIsRelative=1\nPath=abcd.default\nDefault=1\n",
+    );
+    let profile = sandbox.path().join(".mozilla/firefox/abcd.default");
+    std::fs::create_dir_all(&profile).unwrap();
+    let conn = rusqlite::Connection::open(profile.join("cookies.sqlite")).unwrap();
+    conn.execute_batch(&format!(
+        "CREATE TABLE moz_cookies (name TEXT, value TEXT, host TEXT, expiry INTEGER);
+         INSERT INTO moz_cookies VALUES ('session', '{}', '.adventofcode.com', 1);",
+        support::SESSION
+    ))
+    .unwrap();
+    drop(conn);
+
+    let out = sandbox
+        .aocctl()
+        .env_remove("SESSION")
+        .args(["auth", "import", "--browser", "firefox"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{}", output_text(&out));
+    assert_eq!(sandbox.read(".session").trim(), support::SESSION);
+}
+
+#[test]
+fn sync_fetches_missing_days_and_reports_locked_ones() {
+    let mock = MockAoc::start();
+    mock.lock_from(4);
+    let sandbox = Sandbox::new(&mock);
+    sandbox.write("inputs/2025/d02.txt", "already here");
+
+    let out = sandbox.run(&["sync", "2025", "--pages"], "");
+    let text = output_text(&out);
+    assert!(out.status.success(), "{text}");
+
+    assert_eq!(
+        sandbox.read("inputs/2025/d01.txt"),
+        support::INPUT.trim_end()
+    );
+    assert_eq!(sandbox.read("inputs/2025/d02.txt"), "already here");
+    assert_eq!(
+        sandbox.read("inputs/2025/d03.txt"),
+        support::INPUT.trim_end()
+    );
+    assert!(sandbox.read("inputs/2025/d01.html").contains("Mock Puzzle"));
+    assert!(!sandbox.path().join("inputs/2025/d04.txt").exists());
+
+    assert!(
+        text.contains("Inputs: 2 fetched, 1 skipped, 9 locked"),
+        "{text}"
+    );
+    assert!(
+        text.contains("[ 4/12] Day  4: input not unlocked yet"),
+        "{text}"
+    );
+}
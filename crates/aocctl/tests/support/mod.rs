@@ -0,0 +1,444 @@
+//! Test support: a tiny mock of the Advent of Code site and a sandboxed
+//! workspace to run the `aocctl` binary in.
+//!
+//! The mock understands just enough of the real site for the CLI: the events
+//! list, the year calendar, puzzle pages, inputs, the answer endpoint and
+//! private leaderboards. It only accepts requests that carry the [`SESSION`]
+//! cookie, mirroring how the real site reacts to a missing or wrong token.
+//! `POST /webhook` stands in for a notification receiver and accepts anything.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Session token the mock server accepts.
+pub const SESSION: &str = "mock-session-token";
+
+/// Accepted answers for part 1 and part 2.
+pub const ANSWERS: [&str; 2] = ["15", "120"];
+
+pub const INPUT: &str = include_str!("../fixtures/input.txt");
+pub const DAY_PAGE: &str = include_str!("../fixtures/day.html");
+/// Part two's article, shown on the day page once part one is solved.
+pub const DAY_PART_TWO: &str = include_str!("../fixtures/day_part2.html");
+pub const ANSWER_RIGHT: &str = include_str!("../fixtures/answer_right.html");
+pub const ANSWER_WRONG: &str = include_str!("../fixtures/answer_wrong.html");
+pub const ANSWER_TOO_HIGH: &str = include_str!("../fixtures/answer_too_high.html");
+pub const ANSWER_TOO_LOW: &str = include_str!("../fixtures/answer_too_low.html");
+pub const ANSWER_RATE_LIMITED: &str = include_str!("../fixtures/answer_rate_limited.html");
+/// Private leaderboard served for any id.
+pub const LEADERBOARD: &str = include_str!("../fixtures/leaderboard.json");
+
+/// User name shown in the header of authenticated pages.
+pub const USER_NAME: &str = "Mock User";
+
+const LOGGED_OUT: &str =
+    "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+/// A request the mock server received.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path:   String,
+    pub cookie: Option<String>,
+    pub body:   String,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    requests:     Vec<Request>,
+    rate_limited: bool,
+    /// Number of upcoming submissions to rate-limit with a one second wait.
+    throttled:    u32,
+    /// Number of upcoming requests to answer with a 503.
+    failures:     u32,
+    /// First day that is not unlocked yet.
+    locked_from:  Option<u8>,
+    /// Number of upcoming requests to answer as "not unlocked yet".
+    lagging:      u32,
+    /// Parts solved per day; the day page shows their accepted answers.
+    solved:       HashMap<u8, u8>,
+}
+
+/// A mock Advent of Code server listening on a random local port.
+#[derive(Debug, Clone)]
+pub struct MockAoc {
+    base_url: String,
+    state:    Arc<Mutex<State>>,
+}
+
+impl MockAoc {
+    /// Start the server on a background thread.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let shared = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&shared);
+                thread::spawn(move || handle(stream, &state));
+            }
+        });
+
+        Self { base_url, state }
+    }
+
+    /// URL to point `AOC_BASE_URL` at.
+    pub fn base_url(&self) -> &str { &self.base_url }
+
+    /// Answer every submission with the "answer too recently" page.
+    pub fn set_rate_limited(&self, on: bool) { self.state.lock().unwrap().rate_limited = on; }
+
+    /// Rate-limit the next `n` submissions, asking for a one second wait.
+    pub fn rate_limit_next(&self, n: u32) { self.state.lock().unwrap().throttled = n; }
+
+    /// Answer the next `n` requests with `503 Service Unavailable`.
+    pub fn fail_next(&self, n: u32) { self.state.lock().unwrap().failures = n; }
+
+    /// Treat `day` and every later day as not unlocked yet (HTTP 404).
+    pub fn lock_from(&self, day: u8) { self.state.lock().unwrap().locked_from = Some(day); }
+
+    /// Answer the next `n` requests with a 404, like a server that is a few
+    /// seconds behind the unlock instant.
+    pub fn lag_next(&self, n: u32) { self.state.lock().unwrap().lagging = n; }
+
+    /// Mark the first `parts` parts of `day` as already solved.
+    pub fn solve(&self, day: u8, parts: u8) {
+        self.state.lock().unwrap().solved.insert(day, parts);
+    }
+
+    /// Every request received so far, in order.
+    pub fn requests(&self) -> Vec<Request> { self.state.lock().unwrap().requests.clone() }
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>) {
+    let Some(req) = read_request(&stream) else {
+        return;
+    };
+    state.lock().unwrap().requests.push(req.clone());
+
+    let (code, body) = route(&req, state);
+    respond(stream, code, &body);
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut cookie = None;
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).ok()?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header.split_once(':')?;
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => cookie = Some(value.trim().to_string()),
+            "content-length" => length = value.trim().parse().ok()?,
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(Request {
+        method,
+        path,
+        cookie,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn route(req: &Request, state: &Mutex<State>) -> (u16, String) {
+    let segments: Vec<&str> = req.path.trim_matches('/').split('/').collect();
+    let authed = req.cookie.as_deref() == Some(&format!("session={SESSION}"));
+
+    {
+        let mut state = state.lock().unwrap();
+        if state.failures > 0 {
+            state.failures -= 1;
+            return (503, "Service Unavailable".to_string());
+        }
+
+        let day = match segments.as_slice() {
+            [_, "day", day, ..] => day.parse::<u8>().ok(),
+            _ => None,
+        };
+        let locked = state
+            .locked_from
+            .is_some_and(|first| day.is_some_and(|d| d >= first));
+        if locked || state.lagging > 0 {
+            state.lagging = state.lagging.saturating_sub(1);
+            return (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!".to_string(),
+            );
+        }
+    }
+
+    match (req.method.as_str(), segments.as_slice()) {
+        ("GET", [""]) => (200, home(authed)),
+        ("GET", ["events"]) => (200, events_page(authed, state)),
+        ("GET", [year]) if year.parse::<u32>().is_ok() => {
+            (200, calendar_page(year, authed, state))
+        }
+        ("GET", [_, "day", day]) => (200, day_page(day, authed, state)),
+        ("GET", [_, "day", _, "input"]) if authed => (200, INPUT.to_string()),
+        ("GET", [_, "day", _, "input"]) => (400, LOGGED_OUT.to_string()),
+        ("POST", [_, "day", day, "answer"]) if authed => (200, answer(day, &req.body, state)),
+        ("POST", [_, "day", _, "answer"]) => (400, LOGGED_OUT.to_string()),
+        // Logged out, the site redirects to the login page.
+        ("GET", [_, "leaderboard", "private", "view", _]) if authed => {
+            (200, LEADERBOARD.to_string())
+        }
+        ("GET", [_, "leaderboard", "private", "view", _]) => (200, home(false)),
+        ("POST", ["webhook"]) => (200, "ok".to_string()),
+        _ => (404, "404 Not Found".to_string()),
+    }
+}
+
+fn home(authed: bool) -> String {
+    let header = if authed {
+        format!(r#"<div class="user">{USER_NAME} <span class="star-count">2*</span></div>"#)
+    } else {
+        r#"<div><a href="/auth/login">[Log In]</a></div>"#.to_string()
+    };
+    format!("<!DOCTYPE html><html><body><header>{header}</header><main></main></body></html>")
+}
+
+/// Every event from 2015 to 2025. The stars solved through the mock all
+/// count towards 2025; earlier events have none.
+fn events_page(authed: bool, state: &Mutex<State>) -> String {
+    let stars: u32 = if authed {
+        state
+            .lock()
+            .unwrap()
+            .solved
+            .values()
+            .map(|&p| u32::from(p))
+            .sum()
+    } else {
+        0
+    };
+
+    let mut html = String::from("<main>");
+    for year in (2015..=2025).rev() {
+        let count = if year == 2025 && stars > 0 {
+            format!(r#" <span class="star-count">{stars}*</span>"#)
+        } else {
+            String::new()
+        };
+        html.push_str(&format!(
+            r#"<div class="eventlist-event"><a href="/{year}">[{year}]</a>{count}</div>"#
+        ));
+        html.push('\n');
+    }
+    html.push_str("</main>");
+    html
+}
+
+/// The year calendar: one link per unlocked day, labelled with the stars a
+/// logged-in user has earned. Locked days are plain spans.
+fn calendar_page(year: &str, authed: bool, state: &Mutex<State>) -> String {
+    let state = state.lock().unwrap();
+    let days: u8 = if year.parse::<u32>().unwrap() >= 2025 { 12 } else { 25 };
+    let unlocked = state.locked_from.map_or(days, |first| first - 1);
+
+    let mut html = String::from(r#"<main><pre class="calendar">"#);
+    for day in 1..=days {
+        let stars = if authed { state.solved.get(&day).copied().unwrap_or(0) } else { 0 };
+        let (label, class) = match stars {
+            0 => (String::new(), ""),
+            1 => (", one star".to_string(), " calendar-complete"),
+            _ => (", two stars".to_string(), " calendar-verycomplete"),
+        };
+        if day <= unlocked {
+            html.push_str(&format!(
+                r#"<a aria-label="Day {day}{label}" href="/{year}/day/{day}" class="calendar-day{day}{class}">{day:>2}</a>"#
+            ));
+        } else {
+            html.push_str(&format!(
+                r#"<span class="calendar-day{day}">{day:>2}</span>"#
+            ));
+        }
+        html.push('\n');
+    }
+    html.push_str("</pre></main>");
+    html
+}
+
+/// The day page as a logged-in user sees it: part two appears once part one
+/// is solved, followed by the accepted answers.
+fn day_page(day: &str, authed: bool, state: &Mutex<State>) -> String {
+    let solved = day
+        .parse()
+        .ok()
+        .filter(|_| authed)
+        .and_then(|d| state.lock().unwrap().solved.get(&d).copied())
+        .unwrap_or(0);
+
+    let answer = |i: usize| {
+        format!(
+            "<p>Your puzzle answer was <code>{}</code>.</p>\n",
+            ANSWERS[i]
+        )
+    };
+    let revealed = match solved {
+        0 => String::new(),
+        1 => format!("{}{DAY_PART_TWO}", answer(0)),
+        _ => format!("{}{DAY_PART_TWO}{}", answer(0), answer(1)),
+    };
+    DAY_PAGE.replace("<form", &format!("{revealed}<form"))
+}
+
+fn answer(day: &str, form: &str, state: &Mutex<State>) -> String {
+    let mut s = state.lock().unwrap();
+    if s.rate_limited {
+        return ANSWER_RATE_LIMITED.to_string();
+    }
+    if s.throttled > 0 {
+        s.throttled -= 1;
+        return ANSWER_RATE_LIMITED.replace("43s", "1s");
+    }
+    drop(s);
+
+    let field = |key: &str| {
+        form.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.to_string())
+            .unwrap_or_default()
+    };
+
+    let level = field("level");
+    let given = field("answer");
+    let expected = if level == "2" { ANSWERS[1] } else { ANSWERS[0] };
+
+    match (given.parse::<i64>(), expected.parse::<i64>()) {
+        _ if given == expected => {
+            if let Ok(day) = day.parse() {
+                let level = level.parse().unwrap_or(1);
+                let mut s = state.lock().unwrap();
+                let solved = s.solved.entry(day).or_default();
+                *solved = (*solved).max(level);
+            }
+            ANSWER_RIGHT.to_string()
+        }
+        (Ok(g), Ok(e)) if g > e => ANSWER_TOO_HIGH.to_string(),
+        (Ok(g), Ok(e)) if g < e => ANSWER_TOO_LOW.to_string(),
+        _ => ANSWER_WRONG.to_string(),
+    }
+}
+
+fn respond(mut stream: TcpStream, code: u16, body: &str) {
+    let reason = match code {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        503 => "Service Unavailable",
+        _ => "Error",
+    };
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {code} {reason}\r\nContent-Type: text/html\r\nContent-Length: \
+         {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+}
+
+/// A throwaway workspace directory to run `aocctl` in.
+pub struct Sandbox {
+    dir:  tempfile::TempDir,
+    base: String,
+}
+
+impl Sandbox {
+    /// Create an empty workspace whose `aocctl` talks to `mock`.
+    ///
+    /// Request throttling is switched off so tests do not sleep; tests that
+    /// exercise the throttle overwrite `.aocctl/config.toml` themselves.
+    pub fn new(mock: &MockAoc) -> Self {
+        let sandbox = Self {
+            dir:  tempfile::tempdir().expect("create sandbox"),
+            base: mock.base_url().to_string(),
+        };
+        sandbox.write(".aocctl/config.toml", "request_gap_secs = 0\n");
+        sandbox
+    }
+
+    pub fn path(&self) -> &Path { self.dir.path() }
+
+    /// Write a file relative to the workspace root, creating parent dirs.
+    pub fn write(&self, rel: &str, contents: &str) {
+        let path = self.path().join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    /// Read a file relative to the workspace root.
+    pub fn read(&self, rel: &str) -> String {
+        std::fs::read_to_string(self.path().join(rel))
+            .unwrap_or_else(|e| panic!("read {rel}: {e}"))
+    }
+
+    /// An `aocctl` command that runs inside the sandbox against the mock.
+    ///
+    /// `HOME` and `XDG_CONFIG_HOME` point into the sandbox so a developer's
+    /// real session never leaks into the tests.
+    pub fn aocctl(&self) -> Command {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_aocctl"));
+        cmd.current_dir(self.path())
+            .env("AOC_BASE_URL", &self.base)
+            .env("SESSION", SESSION)
+            .env("HOME", self.path())
+            .env("XDG_CONFIG_HOME", self.path().join(".config"))
+            .env_remove("CARGO_TARGET_DIR")
+            .stdin(Stdio::null());
+        cmd
+    }
+
+    /// Run `aocctl` with `args`, feeding `stdin` to it.
+    pub fn run(&self, args: &[&str], stdin: &str) -> Output {
+        let mut child = self
+            .aocctl()
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("spawn aocctl");
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        child.wait_with_output().expect("wait for aocctl")
+    }
+}
+
+/// Combined stdout and stderr of a finished command.
+pub fn output_text(out: &Output) -> String {
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&out.stdout),
+        String::from_utf8_lossy(&out.stderr)
+    )
+}